@@ -12,17 +12,18 @@ macro_rules! dir4 {
         use core::ops::RangeBounds;
         let x: $t = $x.try_into().unwrap();
         let y: $t = $y.try_into().unwrap();
-        vec![
+        let (range_x, range_y) = ($range_x, $range_y);
+        // a fixed array indexed by the moved closure, not to allocate
+        let dirs = [
             (x.checked_sub(1), Some(y)),
             (Some(x), y.checked_sub(1)),
             (x.checked_add(1), Some(y)),
             (Some(x), y.checked_add(1)),
-        ]
-        .into_iter()
-        .filter_map(|p| match p {
+        ];
+        (0..dirs.len()).filter_map(move |i| match dirs[i] {
             (Some(x), Some(y))
-                if RangeBounds::<$t>::contains(&$range_x, &x)
-                    && RangeBounds::<$t>::contains(&$range_y, &y) =>
+                if RangeBounds::<$t>::contains(&range_x, &x)
+                    && RangeBounds::<$t>::contains(&range_y, &y) =>
             {
                 Some((x, y))
             }
@@ -45,18 +46,18 @@ macro_rules! dir8 {
         use core::ops::RangeBounds;
         let x: $t = $x.try_into().unwrap();
         let y: $t = $y.try_into().unwrap();
-        let ys = vec![y.checked_sub(1), Some(y), y.checked_add(1)];
-        vec![x.checked_sub(1), Some(x), x.checked_add(1)]
-            .into_iter()
-            .map(move |x| core::iter::repeat(x).zip(ys.clone().into_iter()))
-            .flatten()
-            .filter_map(move |p| match p {
-                (Some(x2), Some(y2))
-                    if RangeBounds::<$t>::contains(&$range_x, &x2)
-                        && RangeBounds::<$t>::contains(&$range_y, &y2)
-                        && !(x2 == x && y2 == y) =>
+        let (range_x, range_y) = ($range_x, $range_y);
+        let xs = [x.checked_sub(1), Some(x), x.checked_add(1)];
+        let ys = [y.checked_sub(1), Some(y), y.checked_add(1)];
+        // `4` is `(x, y)` itself
+        (0..9)
+            .filter(|&i| i != 4)
+            .filter_map(move |i| match (xs[i / 3], ys[i % 3]) {
+                (Some(x), Some(y))
+                    if RangeBounds::<$t>::contains(&range_x, &x)
+                        && RangeBounds::<$t>::contains(&range_y, &y) =>
                 {
-                    Some((x2, y2))
+                    Some((x, y))
                 }
                 _ => None,
             })
@@ -8,3 +8,8 @@ pub use btree::BTreeMultiSet;
 mod hash;
 #[codesnip::entry("HashMultiSet")]
 pub use hash::HashMultiSet;
+
+#[cfg_attr(nightly, codesnip::entry("OrderStatMultiSet"))]
+mod order_stat;
+#[codesnip::entry("OrderStatMultiSet")]
+pub use order_stat::OrderStatMultiSet;
@@ -1,8 +1,13 @@
 use core::borrow::Borrow;
 use core::fmt::Debug;
 use core::hash::{BuildHasher, Hash};
-use core::iter::{FromIterator, FusedIterator};
+use core::iter::{repeat, FromIterator, FusedIterator};
 use core::mem::swap;
+use core::ops::{
+    Add, BitAnd, BitOr,
+    Bound::{Included, Unbounded},
+    RangeBounds, Sub,
+};
 use std::collections::{
     btree_set::{self, BTreeSet},
     hash_map::{HashMap, RandomState},
@@ -57,6 +62,38 @@ impl<T, S> BTreeMultiSet<T, S> {
     {
         self.tree.get(value)
     }
+
+    pub fn first(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.tree.iter().next()
+    }
+
+    pub fn last(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.tree.iter().next_back()
+    }
+
+    /// Returns the smallest element not less than `value`.
+    pub fn lower_bound<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.tree.range((Included(value), Unbounded)).next()
+    }
+
+    /// Returns the largest element not greater than `value`.
+    pub fn floor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.tree.range((Unbounded, Included(value))).next_back()
+    }
 }
 
 impl<T, S> BTreeMultiSet<T, S>
@@ -80,12 +117,25 @@ where
         self.tree.insert(value)
     }
 
+    pub fn insert_times(&mut self, value: T, count: usize)
+    where
+        T: Clone + Ord + Hash,
+    {
+        if count == 0 {
+            return;
+        }
+        self.len += count;
+        *self.counter.entry(value.clone()).or_insert(0) += count;
+        self.tree.insert(value);
+    }
+
     pub fn remove<Q>(&mut self, value: &Q) -> bool
     where
         T: Borrow<Q> + Ord + Hash,
         Q: Ord + Hash,
     {
         if let Some(count) = self.counter.get_mut(value) {
+            self.len -= 1;
             *count -= 1;
             if *count == 0 {
                 self.tree.remove(value);
@@ -97,12 +147,28 @@ where
         }
     }
 
+    /// Removes all copies of `value`, and returns the number of them.
+    pub fn remove_all<Q>(&mut self, value: &Q) -> usize
+    where
+        T: Borrow<Q> + Ord + Hash,
+        Q: Ord + Hash,
+    {
+        if let Some(count) = self.counter.remove(value) {
+            self.len -= count;
+            self.tree.remove(value);
+            count
+        } else {
+            0
+        }
+    }
+
     pub fn take<Q>(&mut self, value: &Q) -> Option<T>
     where
         T: Borrow<Q> + Ord + Clone + Hash,
         Q: Ord + Hash,
     {
         if let Some(count) = self.counter.get_mut(value) {
+            self.len -= 1;
             *count -= 1;
             if *count == 0 {
                 self.counter.remove(value);
@@ -115,6 +181,24 @@ where
         }
     }
 
+    /// Removes and returns one copy of the smallest element.
+    pub fn pop_first(&mut self) -> Option<T>
+    where
+        T: Ord + Clone + Hash,
+    {
+        let value = self.first()?.clone();
+        self.take(&value)
+    }
+
+    /// Removes and returns one copy of the largest element.
+    pub fn pop_last(&mut self) -> Option<T>
+    where
+        T: Ord + Clone + Hash,
+    {
+        let value = self.last()?.clone();
+        self.take(&value)
+    }
+
     pub fn iter(&self) -> Iter<'_, T, S>
     where
         T: Eq + Hash,
@@ -127,6 +211,89 @@ where
         }
     }
 
+    /// Returns an iterator over the elements in `range` in ascending order,
+    /// including all copies of each element.
+    pub fn range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = &T>
+    where
+        T: Borrow<Q> + Ord + Hash,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let counter = &self.counter;
+        self.tree.range(range).flat_map(move |value| {
+            repeat(value).take(counter.get::<T>(value).copied().unwrap_or_default())
+        })
+    }
+
+    /// Attaches the counts `f(self.count, other.count)` to `values`, skipping the zeros.
+    fn merge_counts<'a, I>(
+        &'a self,
+        other: &'a Self,
+        values: I,
+        f: fn(usize, usize) -> usize,
+    ) -> impl Iterator<Item = (&'a T, usize)> + 'a
+    where
+        T: Eq + Hash,
+        I: Iterator<Item = &'a T> + 'a,
+    {
+        values
+            .map(move |value| (value, f(self.count(value), other.count(value))))
+            .filter(|&(_, count)| count > 0)
+    }
+
+    fn collect_counts<'a, I>(iter: I) -> Self
+    where
+        T: 'a + Clone + Ord + Hash,
+        S: Default,
+        I: Iterator<Item = (&'a T, usize)>,
+    {
+        let mut ret = Self::default();
+        for (value, count) in iter {
+            ret.insert_times(value.clone(), count);
+        }
+        ret
+    }
+
+    /// Returns an iterator over the union in ascending order, where each value appears
+    /// as many times as in whichever of `self` and `other` has more.
+    pub fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Ord + Hash,
+    {
+        self.merge_counts(other, self.tree.union(&other.tree), usize::max)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
+    /// Returns an iterator over the intersection in ascending order, where each value appears
+    /// as many times as in whichever of `self` and `other` has fewer.
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Ord + Hash,
+    {
+        self.merge_counts(other, self.tree.intersection(&other.tree), usize::min)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
+    /// Returns an iterator over the values in `self` with their copies in `other` removed,
+    /// in ascending order.
+    pub fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Ord + Hash,
+    {
+        self.merge_counts(other, self.tree.iter(), usize::saturating_sub)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
+    /// Returns an iterator over the values in both `self` and `other` in ascending order,
+    /// adding their counts.
+    pub fn sum<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Ord + Hash,
+    {
+        self.merge_counts(other, self.tree.union(&other.tree), |a, b| a + b)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
     pub fn is_disjoint(&self, other: &BTreeMultiSet<T, S>) -> bool
     where
         T: Ord,
@@ -179,6 +346,68 @@ where
     }
 }
 
+impl<T, S> BitOr<&BTreeMultiSet<T, S>> for &BTreeMultiSet<T, S>
+where
+    T: Clone + Ord + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = BTreeMultiSet<T, S>;
+
+    fn bitor(self, rhs: &BTreeMultiSet<T, S>) -> Self::Output {
+        BTreeMultiSet::collect_counts(self.merge_counts(
+            rhs,
+            self.tree.union(&rhs.tree),
+            usize::max,
+        ))
+    }
+}
+
+impl<T, S> BitAnd<&BTreeMultiSet<T, S>> for &BTreeMultiSet<T, S>
+where
+    T: Clone + Ord + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = BTreeMultiSet<T, S>;
+
+    fn bitand(self, rhs: &BTreeMultiSet<T, S>) -> Self::Output {
+        BTreeMultiSet::collect_counts(self.merge_counts(
+            rhs,
+            self.tree.intersection(&rhs.tree),
+            usize::min,
+        ))
+    }
+}
+
+impl<T, S> Sub<&BTreeMultiSet<T, S>> for &BTreeMultiSet<T, S>
+where
+    T: Clone + Ord + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = BTreeMultiSet<T, S>;
+
+    fn sub(self, rhs: &BTreeMultiSet<T, S>) -> Self::Output {
+        BTreeMultiSet::collect_counts(self.merge_counts(
+            rhs,
+            self.tree.iter(),
+            usize::saturating_sub,
+        ))
+    }
+}
+
+impl<T, S> Add<&BTreeMultiSet<T, S>> for &BTreeMultiSet<T, S>
+where
+    T: Clone + Ord + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = BTreeMultiSet<T, S>;
+
+    fn add(self, rhs: &BTreeMultiSet<T, S>) -> Self::Output {
+        BTreeMultiSet::collect_counts(
+            self.merge_counts(rhs, self.tree.union(&rhs.tree), |a, b| a + b),
+        )
+    }
+}
+
 impl<T, S> Default for BTreeMultiSet<T, S>
 where
     T: Ord + Hash,
@@ -432,4 +661,112 @@ mod tests {
         set.insert(5);
         assert!(!set.is_subset(&sup));
     }
+
+    #[test]
+    fn ordered() {
+        let mut set = NAPIER.iter().copied().collect::<BTreeMultiSet<_>>();
+        let mut sorted = NAPIER.to_vec();
+        sorted.sort();
+        assert_eq!(set.first(), sorted.first());
+        assert_eq!(set.last(), sorted.last());
+        assert_eq!(
+            set.range(3..6).copied().collect::<Vec<_>>(),
+            sorted
+                .iter()
+                .copied()
+                .filter(|x| (3..6).contains(x))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(set.range(..=2).next_back(), Some(&2));
+
+        for i in 0..NAPIER.len() {
+            if i % 2 == 0 {
+                assert_eq!(set.pop_first(), Some(sorted.remove(0)));
+            } else {
+                assert_eq!(set.pop_last(), sorted.pop());
+            }
+            assert_eq!(set.len(), sorted.len());
+            if let (Some(first), Some(last)) = (sorted.first(), sorted.last()) {
+                assert_eq!(set.first(), Some(first));
+                assert_eq!(set.last(), Some(last));
+            }
+        }
+        assert_eq!(set.pop_first(), None);
+        assert_eq!(set.pop_last(), None);
+    }
+
+    #[test]
+    fn bounds() {
+        let mut set = vec![1, 3, 3, 7].into_iter().collect::<BTreeMultiSet<_>>();
+        assert_eq!(set.lower_bound(&0), Some(&1));
+        assert_eq!(set.lower_bound(&2), Some(&3));
+        assert_eq!(set.lower_bound(&3), Some(&3));
+        assert_eq!(set.lower_bound(&8), None);
+        assert_eq!(set.floor(&0), None);
+        assert_eq!(set.floor(&6), Some(&3));
+        assert_eq!(set.floor(&7), Some(&7));
+
+        set.insert_times(5, 3);
+        set.insert_times(9, 0);
+        assert_eq!(set.len(), 7);
+        assert_eq!(set.count(&5), 3);
+        assert!(!set.contains(&9));
+        assert_eq!(set.remove_all(&3), 2);
+        assert_eq!(set.remove_all(&3), 0);
+        assert_eq!(set.len(), 5);
+        assert_eq!(set.take(&5), Some(5));
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), vec![1, 5, 5, 7]);
+    }
+
+    #[test]
+    fn algebra() {
+        let (head, tail) = NAPIER.split_at(400);
+        let a = head
+            .iter()
+            .copied()
+            .filter(|&x| x != 3)
+            .collect::<BTreeMultiSet<_>>();
+        let b = tail
+            .iter()
+            .copied()
+            .filter(|&x| x != 4)
+            .collect::<BTreeMultiSet<_>>();
+        let count = |set: &BTreeMultiSet<usize>, x: usize| set.iter().filter(|&&y| y == x).count();
+        let expected = |f: fn(usize, usize) -> usize| {
+            let mut ret = BTreeMultiSet::new();
+            for x in 0..10 {
+                let c = f(count(&a, x), count(&b, x));
+                if c > 0 {
+                    ret.insert_times(x, c);
+                }
+            }
+            ret
+        };
+
+        let union = expected(usize::max);
+        assert_eq!(a.union(&b).copied().collect::<BTreeMultiSet<_>>(), union);
+        assert_eq!(&a | &b, union);
+        let intersection = expected(usize::min);
+        assert_eq!(
+            a.intersection(&b).copied().collect::<BTreeMultiSet<_>>(),
+            intersection
+        );
+        assert_eq!(&a & &b, intersection);
+        let difference = expected(usize::saturating_sub);
+        assert_eq!(
+            a.difference(&b).copied().collect::<BTreeMultiSet<_>>(),
+            difference
+        );
+        assert_eq!(&a - &b, difference);
+        let sum = expected(|x, y| x + y);
+        assert_eq!(a.sum(&b).copied().collect::<BTreeMultiSet<_>>(), sum);
+        assert_eq!(&a + &b, sum);
+        assert_eq!(sum.len(), a.len() + b.len());
+        let sorted = |v: Vec<&usize>| v.windows(2).all(|w| w[0] <= w[1]);
+        assert!(sorted(a.union(&b).collect()));
+        assert!(sorted(a.intersection(&b).collect()));
+        assert!(sorted(a.difference(&b).collect()));
+        assert!(sorted(a.sum(&b).collect()));
+    }
 }
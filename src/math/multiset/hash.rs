@@ -1,7 +1,8 @@
 use core::borrow::Borrow;
 use core::fmt::{Debug, Formatter, Result};
 use core::hash::{BuildHasher, Hash};
-use core::iter::{FromIterator, FusedIterator};
+use core::iter::{repeat, FromIterator, FusedIterator};
+use core::ops::{Add, BitAnd, BitOr, Sub};
 use std::collections::hash_map::{self, HashMap, RandomState};
 
 pub struct HashMultiSet<T, S = RandomState> {
@@ -88,6 +89,65 @@ where
     {
         self.counter.get(value).copied()
     }
+
+    /// Returns the distinct values in `self` (and `other` if `both`),
+    /// with the counts `f(self.count, other.count)` which are positive.
+    fn merge_counts<'a>(
+        &'a self,
+        other: &'a Self,
+        both: bool,
+        f: fn(usize, usize) -> usize,
+    ) -> impl Iterator<Item = (&'a T, usize)> + 'a {
+        let count = |set: &Self, value: &T| set.counter.get(value).copied().unwrap_or(0);
+        let only_other = other
+            .counter
+            .iter()
+            .filter(move |&(value, _)| both && !self.counter.contains_key(value))
+            .map(move |(value, &c)| (value, f(0, c)));
+        self.counter
+            .iter()
+            .map(move |(value, &c)| (value, f(c, count(other, value))))
+            .chain(only_other)
+            .filter(|&(_, c)| c > 0)
+    }
+
+    fn collect_counts<'a, I>(iter: I) -> Self
+    where
+        T: 'a + Clone,
+        S: Default,
+        I: Iterator<Item = (&'a T, usize)>,
+    {
+        let mut ret = Self::new();
+        for (value, count) in iter {
+            ret.insert_times(value.clone(), count);
+        }
+        ret
+    }
+
+    /// Returns an iterator over the union, where each value appears as many times as in
+    /// whichever of `self` and `other` has more.
+    pub fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.merge_counts(other, true, usize::max)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
+    /// Returns an iterator over the intersection, where each value appears as many times as in
+    /// whichever of `self` and `other` has fewer.
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.merge_counts(other, false, usize::min)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
+    /// Returns an iterator over the values in `self` with their copies in `other` removed.
+    pub fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.merge_counts(other, false, usize::saturating_sub)
+            .flat_map(|(value, count)| repeat(value).take(count))
+    }
+
+    /// Returns an iterator over the values in both `self` and `other`, adding their counts.
+    pub fn sum<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter().chain(other.iter())
+    }
 }
 
 impl<T: Clone + Debug, S> Debug for HashMultiSet<T, S> {
@@ -136,6 +196,54 @@ impl<'a, T, S> IntoIterator for &'a HashMultiSet<T, S> {
     }
 }
 
+impl<T, S> BitOr<&HashMultiSet<T, S>> for &HashMultiSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = HashMultiSet<T, S>;
+
+    fn bitor(self, rhs: &HashMultiSet<T, S>) -> Self::Output {
+        HashMultiSet::collect_counts(self.merge_counts(rhs, true, usize::max))
+    }
+}
+
+impl<T, S> BitAnd<&HashMultiSet<T, S>> for &HashMultiSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = HashMultiSet<T, S>;
+
+    fn bitand(self, rhs: &HashMultiSet<T, S>) -> Self::Output {
+        HashMultiSet::collect_counts(self.merge_counts(rhs, false, usize::min))
+    }
+}
+
+impl<T, S> Sub<&HashMultiSet<T, S>> for &HashMultiSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = HashMultiSet<T, S>;
+
+    fn sub(self, rhs: &HashMultiSet<T, S>) -> Self::Output {
+        HashMultiSet::collect_counts(self.merge_counts(rhs, false, usize::saturating_sub))
+    }
+}
+
+impl<T, S> Add<&HashMultiSet<T, S>> for &HashMultiSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: Default + BuildHasher,
+{
+    type Output = HashMultiSet<T, S>;
+
+    fn add(self, rhs: &HashMultiSet<T, S>) -> Self::Output {
+        HashMultiSet::collect_counts(self.merge_counts(rhs, true, |a, b| a + b))
+    }
+}
+
 impl<T> FromIterator<T> for HashMultiSet<T>
 where
     T: Eq + Hash,
@@ -306,4 +414,50 @@ mod tests {
             assert_eq!(count[i], mset.count(&i).unwrap_or(0), "i={}", i);
         }
     }
+
+    #[test]
+    fn algebra() {
+        let (head, tail) = NAPIER.split_at(400);
+        let a = head
+            .iter()
+            .copied()
+            .filter(|&x| x != 3)
+            .collect::<HashMultiSet<_>>();
+        let b = tail
+            .iter()
+            .copied()
+            .filter(|&x| x != 4)
+            .collect::<HashMultiSet<_>>();
+        let count = |set: &HashMultiSet<usize>, x: usize| set.iter().filter(|&&y| y == x).count();
+        let expected = |f: fn(usize, usize) -> usize| {
+            let mut ret = HashMultiSet::new();
+            for x in 0..10 {
+                let c = f(count(&a, x), count(&b, x));
+                if c > 0 {
+                    ret.insert_times(x, c);
+                }
+            }
+            ret
+        };
+
+        let union = expected(usize::max);
+        assert_eq!(a.union(&b).copied().collect::<HashMultiSet<_>>(), union);
+        assert_eq!(&a | &b, union);
+        let intersection = expected(usize::min);
+        assert_eq!(
+            a.intersection(&b).copied().collect::<HashMultiSet<_>>(),
+            intersection
+        );
+        assert_eq!(&a & &b, intersection);
+        let difference = expected(usize::saturating_sub);
+        assert_eq!(
+            a.difference(&b).copied().collect::<HashMultiSet<_>>(),
+            difference
+        );
+        assert_eq!(&a - &b, difference);
+        let sum = expected(|x, y| x + y);
+        assert_eq!(a.sum(&b).copied().collect::<HashMultiSet<_>>(), sum);
+        assert_eq!(&a + &b, sum);
+        assert_eq!(sum.len(), a.len() + b.len());
+    }
 }
@@ -0,0 +1,352 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering::{Equal, Greater, Less};
+use core::iter::FromIterator;
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+struct Node<T> {
+    value: T,
+    count: usize,
+    // the number of elements in the subtree
+    size: usize,
+    priority: u64,
+    children: [Tree<T>; 2],
+}
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+fn size<T>(tree: &Tree<T>) -> usize {
+    tree.as_ref().map_or(0, |node| node.size)
+}
+
+impl<T> Node<T> {
+    fn update(&mut self) {
+        self.size = size(&self.children[0]) + self.count + size(&self.children[1]);
+    }
+}
+
+/// Lifts `children[dir]` of the root of `tree` up to the root.
+fn rotate<T>(tree: &mut Tree<T>, dir: usize) {
+    let mut node = tree.take().unwrap();
+    let mut child = node.children[dir].take().unwrap();
+    node.children[dir] = child.children[1 - dir].take();
+    node.update();
+    child.children[1 - dir] = Some(node);
+    child.update();
+    *tree = Some(child);
+}
+
+fn insert<T: Ord>(tree: &mut Tree<T>, value: T, priority: u64) -> bool {
+    let node = match tree {
+        Some(node) => node,
+        None => {
+            *tree = Some(Box::new(Node {
+                value,
+                count: 1,
+                size: 1,
+                priority,
+                children: [None, None],
+            }));
+            return true;
+        }
+    };
+    node.size += 1;
+    let dir = match value.cmp(&node.value) {
+        Equal => {
+            node.count += 1;
+            return false;
+        }
+        Less => 0,
+        Greater => 1,
+    };
+    let inserted = insert(&mut node.children[dir], value, priority);
+    if node.children[dir].as_ref().unwrap().priority > node.priority {
+        rotate(tree, dir);
+    }
+    inserted
+}
+
+/// Removes the root of `tree`.
+fn delete<T>(tree: &mut Tree<T>) {
+    let node = tree.as_mut().unwrap();
+    let dir = match &node.children {
+        [None, _] => {
+            *tree = node.children[1].take();
+            return;
+        }
+        [_, None] => {
+            *tree = node.children[0].take();
+            return;
+        }
+        [Some(left), Some(right)] => {
+            if left.priority > right.priority {
+                0
+            } else {
+                1
+            }
+        }
+    };
+    rotate(tree, dir);
+    let root = tree.as_mut().unwrap();
+    delete(&mut root.children[1 - dir]);
+    root.update();
+}
+
+fn remove_one<T, Q>(tree: &mut Tree<T>, value: &Q) -> bool
+where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let node = match tree {
+        Some(node) => node,
+        None => return false,
+    };
+    let dir = match value.cmp(node.value.borrow()) {
+        Equal => {
+            if node.count > 1 {
+                node.count -= 1;
+                node.size -= 1;
+            } else {
+                delete(tree);
+            }
+            return true;
+        }
+        Less => 0,
+        Greater => 1,
+    };
+    let removed = remove_one(&mut node.children[dir], value);
+    if removed {
+        node.size -= 1;
+    }
+    removed
+}
+
+/// A multiset answering the order statistics in O(log(*n*)),
+/// based on a treap with the number of elements in each subtree.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::math::multiset::OrderStatMultiSet;
+///
+/// let mut set = vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter().collect::<OrderStatMultiSet<_>>();
+/// assert_eq!(set.nth(0), Some(&1));
+/// assert_eq!(set.nth(2), Some(&2));
+/// assert_eq!(set.rank(&4), 4);
+/// assert_eq!(set.count_range(2..=5), 4);
+/// assert!(set.remove_one(&1));
+/// assert_eq!(set.count(&1), 1);
+/// assert_eq!(set.nth(7), None);
+/// ```
+pub struct OrderStatMultiSet<T> {
+    root: Tree<T>,
+    seed: u64,
+}
+
+impl<T> OrderStatMultiSet<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            seed: 0x9e37_79b9_7f4a_7c15,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    /// Returns the `k`-th smallest element (0-indexed).
+    pub fn nth(&self, k: usize) -> Option<&T> {
+        let mut k = k;
+        let mut tree = &self.root;
+        while let Some(node) = tree {
+            let left = size(&node.children[0]);
+            if k < left {
+                tree = &node.children[0];
+            } else if k < left + node.count {
+                return Some(&node.value);
+            } else {
+                k -= left + node.count;
+                tree = &node.children[1];
+            }
+        }
+        None
+    }
+
+    /// Returns the number of elements less than `value`, or not greater if `inclusive`.
+    fn count_less<Q>(&self, value: &Q, inclusive: bool) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut ret = 0;
+        let mut tree = &self.root;
+        while let Some(node) = tree {
+            let go_right = match value.cmp(node.value.borrow()) {
+                Less => false,
+                Equal => inclusive,
+                Greater => true,
+            };
+            if go_right {
+                ret += size(&node.children[0]) + node.count;
+                tree = &node.children[1];
+            } else {
+                tree = &node.children[0];
+            }
+        }
+        ret
+    }
+
+    /// Returns the number of elements less than `value`.
+    pub fn rank<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_less(value, false)
+    }
+
+    pub fn count<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_less(value, true) - self.count_less(value, false)
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count(value) > 0
+    }
+
+    /// Returns the number of elements in `range`.
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let l = match range.start_bound() {
+            Included(l) => self.count_less(l, false),
+            Excluded(l) => self.count_less(l, true),
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(r) => self.count_less(r, true),
+            Excluded(r) => self.count_less(r, false),
+            Unbounded => self.len(),
+        };
+        r.saturating_sub(l)
+    }
+
+    /// Inserts `value`, and returns whether it was not contained.
+    pub fn insert(&mut self, value: T) -> bool
+    where
+        T: Ord,
+    {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        insert(&mut self.root, value, self.seed)
+    }
+
+    /// Removes one of `value`, and returns whether it was contained.
+    pub fn remove_one<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove_one(&mut self.root, value)
+    }
+}
+
+impl<T> Default for OrderStatMultiSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OrderStatMultiSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = Self::new();
+        for value in iter {
+            ret.insert(value);
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderStatMultiSet;
+
+    #[test]
+    fn order_statistics() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut rand = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+
+        let mut set = OrderStatMultiSet::new();
+        let mut sorted: Vec<u64> = vec![];
+        for _ in 0..3000 {
+            let x = rand(30);
+            if rand(3) == 0 {
+                let pos = sorted.binary_search(&x);
+                assert_eq!(set.remove_one(&x), pos.is_ok());
+                if let Ok(pos) = pos {
+                    sorted.remove(pos);
+                }
+            } else {
+                assert_eq!(set.insert(x), !sorted.contains(&x));
+                let pos = sorted.binary_search(&x).unwrap_or_else(|pos| pos);
+                sorted.insert(pos, x);
+            }
+
+            assert_eq!(set.len(), sorted.len());
+            let k = rand(sorted.len() as u64 + 1) as usize;
+            assert_eq!(set.nth(k), sorted.get(k));
+            let y = rand(32);
+            let less = sorted.iter().filter(|&&z| z < y).count();
+            let equal = sorted.iter().filter(|&&z| z == y).count();
+            assert_eq!(set.rank(&y), less);
+            assert_eq!(set.count(&y), equal);
+            assert_eq!(set.contains(&y), equal > 0);
+            let z = rand(32);
+            let in_range = |r: &dyn Fn(u64) -> bool| sorted.iter().filter(|&&w| r(w)).count();
+            assert_eq!(set.count_range(y..z), in_range(&|w| y <= w && w < z));
+            assert_eq!(set.count_range(y..=z), in_range(&|w| y <= w && w <= z));
+            assert_eq!(set.count_range(..z), in_range(&|w| w < z));
+            assert_eq!(set.count_range(y..), in_range(&|w| y <= w));
+        }
+    }
+
+    #[test]
+    fn borrow() {
+        let mut set = ["b", "a", "c", "a"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<OrderStatMultiSet<String>>();
+        assert_eq!(set.rank("b"), 2);
+        assert_eq!(set.count("a"), 2);
+        assert!(set.remove_one("a"));
+        assert_eq!(set.nth(1).map(String::as_str), Some("b"));
+        assert_eq!(set.count_range::<str, _>(..), 3);
+    }
+}
@@ -8,7 +8,7 @@ pub mod element;
 pub use element::Reciprocal;
 
 pub mod alge_struct;
-pub use alge_struct::{Group, Monoid, Semigroup};
+pub use alge_struct::{monoid_pow, Group, Idempotent, Monoid, Semigroup};
 
 #[cfg_attr(nightly, codesnip::entry("AllBitOne"))]
 pub mod all_bit_one;
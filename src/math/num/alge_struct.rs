@@ -2,9 +2,12 @@ pub mod semigroup;
 pub use semigroup::Semigroup;
 
 pub mod monoid;
-pub use monoid::Monoid;
+pub use monoid::{monoid_pow, Monoid};
 
 pub mod group;
 pub use group::Group;
 
+pub mod idempotent;
+pub use idempotent::Idempotent;
+
 pub mod types;
@@ -0,0 +1,11 @@
+use super::Semigroup;
+
+/// Marker for a [`Semigroup`] whose operation is idempotent.
+///
+/// # Idempotence
+///
+/// ~~~text
+/// ∀ a ∈ Set, a ◦ a = a
+/// ~~~
+#[codesnip::entry("Idempotent", include("Semigroup"))]
+pub trait Idempotent: Semigroup {}
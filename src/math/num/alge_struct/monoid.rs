@@ -11,3 +11,50 @@ use super::Semigroup;
 pub trait Monoid: Semigroup {
     fn id() -> Self::Set;
 }
+
+/// Folds `n` copies of `x` by repeated squaring in O(log(*n*)) operations.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::math::num::alge_struct::types::{AddMonoid, MulMonoid};
+/// use lib_rust::math::num::monoid_pow;
+///
+/// assert_eq!(monoid_pow::<AddMonoid<u64>>(&3, 5), 15);
+/// assert_eq!(monoid_pow::<MulMonoid<u64>>(&3, 5), 243);
+/// assert_eq!(monoid_pow::<MulMonoid<u64>>(&3, 0), 1);
+/// ```
+#[codesnip::entry("monoid_pow", include("Monoid"))]
+pub fn monoid_pow<M: Monoid>(x: &M::Set, mut n: usize) -> M::Set {
+    let mut base = x.clone();
+    let mut acc = M::id();
+    while n > 0 {
+        if n & 1 == 1 {
+            acc = M::operate(&acc, &base);
+        }
+        n >>= 1;
+        if n > 0 {
+            base = M::operate(&base, &base);
+        }
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::monoid_pow;
+    use crate::math::num::alge_struct::types::{AddMonoid, BitXorMonoid, MinMonoid, MulMonoid};
+
+    #[test]
+    fn pow() {
+        for n in 0..20 {
+            assert_eq!(monoid_pow::<AddMonoid<usize>>(&3, n), 3 * n);
+            assert_eq!(monoid_pow::<MulMonoid<usize>>(&3, n), 3usize.pow(n as u32));
+            assert_eq!(monoid_pow::<BitXorMonoid<usize>>(&3, n), 3 * (n % 2));
+            assert_eq!(
+                monoid_pow::<MinMonoid<usize>>(&3, n),
+                if n == 0 { core::usize::MAX } else { 3 }
+            );
+        }
+    }
+}
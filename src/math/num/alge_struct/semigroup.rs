@@ -12,3 +12,10 @@ pub trait Semigroup {
     /// It must be satisfy [associativity](https://en.wikipedia.org/wiki/Associative_property) `(a * b) * c = a * (b * c)`.
     fn operate(lhs: &Self::Set, rhs: &Self::Set) -> Self::Set;
 }
+
+/// The trivial semigroup on `()`, used as an aggregate carrying no data.
+#[codesnip::entry("UnitSemigroup", include("Semigroup"))]
+impl Semigroup for () {
+    type Set = ();
+    fn operate(_: &Self::Set, _: &Self::Set) -> Self::Set {}
+}
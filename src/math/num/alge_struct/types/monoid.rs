@@ -1,4 +1,6 @@
-use crate::math::num::{AllBitOne, BoundedAbove, BoundedBelow, Monoid, One, Semigroup, Zero};
+use crate::math::num::{
+    AllBitOne, BoundedAbove, BoundedBelow, Idempotent, Monoid, One, Semigroup, Zero,
+};
 
 #[cfg_attr(nightly, codesnip::entry(include("Monoid", "Semigroup")))]
 #[macro_export]
@@ -45,6 +47,8 @@ define_monoid! {
     BoundedBelow::lower_bound(),
     mod max_monoid_impl
 }
+#[codesnip::entry("MaxMonoid", include("Idempotent"))]
+impl<T: Clone + Ord + BoundedBelow> Idempotent for MaxMonoid<T> {}
 
 #[codesnip::entry("MinMonoid", include("define_monoid", "BoundedAbove"))]
 define_monoid! {
@@ -53,6 +57,38 @@ define_monoid! {
     BoundedAbove::upper_bound(),
     mod min_monoid_impl
 }
+#[codesnip::entry("MinMonoid", include("Idempotent"))]
+impl<T: Clone + Ord + BoundedAbove> Idempotent for MinMonoid<T> {}
+
+pub use min_max_impl::MinMax;
+#[codesnip::entry(inline, "MinMax", include("MinMonoid", "MaxMonoid"))]
+mod min_max_impl {
+    #[codesnip::skip]
+    use super::{MaxMonoid, MinMonoid};
+    use crate::math::num::{BoundedAbove, BoundedBelow, Idempotent, Monoid};
+
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    /// The monoids taking the minimum or the maximum, i.e. [`MinMonoid`] and [`MaxMonoid`].
+    ///
+    /// This trait is sealed, so that other idempotent monoids cannot be mistaken for them.
+    pub trait MinMax: Idempotent + Monoid + sealed::Sealed {
+        /// `true` for [`MinMonoid`] and `false` for [`MaxMonoid`].
+        const IS_MIN: bool;
+    }
+
+    impl<T: Clone + Ord + BoundedAbove> sealed::Sealed for MinMonoid<T> {}
+    impl<T: Clone + Ord + BoundedAbove> MinMax for MinMonoid<T> {
+        const IS_MIN: bool = true;
+    }
+
+    impl<T: Clone + Ord + BoundedBelow> sealed::Sealed for MaxMonoid<T> {}
+    impl<T: Clone + Ord + BoundedBelow> MinMax for MaxMonoid<T> {
+        const IS_MIN: bool = false;
+    }
+}
 
 #[codesnip::entry("BitXorMonoid", include("define_monoid", "Zero"))]
 define_monoid! {
@@ -69,6 +105,8 @@ define_monoid! {
     Zero::zero(),
     mod bitor_monoid_impl { use core::ops::BitOr; }
 }
+#[codesnip::entry("BitOrMonoid", include("Idempotent"))]
+impl<T: Clone + Zero + core::ops::BitOr<Output = T>> Idempotent for BitOrMonoid<T> {}
 
 #[codesnip::entry("BitAndMonoid", include("define_monoid", "AllBitOne"))]
 define_monoid! {
@@ -77,3 +115,15 @@ define_monoid! {
     AllBitOne::ALL_BIT_ONE,
     mod bitand_monoid_impl { use core::ops::BitAnd; }
 }
+#[codesnip::entry("BitAndMonoid", include("Idempotent"))]
+impl<T: Clone + AllBitOne + core::ops::BitAnd<Output = T>> Idempotent for BitAndMonoid<T> {}
+
+#[codesnip::entry("GcdMonoid", include("define_monoid", "Zero", "GcdLcm", "Idempotent"))]
+define_monoid! {
+    GcdMonoid<T: Clone, Zero, GcdLcm<Output = T>>,
+    |lhs,rhs| GcdLcm::gcd(lhs.clone(), rhs.clone()),
+    Zero::zero(),
+    mod gcd_monoid_impl { use crate::math::GcdLcm; }
+}
+#[codesnip::entry("GcdMonoid")]
+impl<T: Clone + Zero + crate::math::GcdLcm<Output = T>> Idempotent for GcdMonoid<T> {}
@@ -1,11 +1,43 @@
 use crate::math::num::Monoid;
-#[cfg_attr(nightly, codesnip::entry("BinaryIndexedTree", include("Monoid")))]
+#[cfg_attr(nightly, codesnip::entry("BinaryIndexedTree", include("Group", "AddGroup", "monoid_pow")))]
 pub mod binary_indexed_tree;
 #[codesnip::entry("BinaryIndexedTree")]
 #[allow(unused_imports)]
 use binary_indexed_tree::{BinaryIndexedTree, BIT};
 
-#[cfg_attr(nightly, codesnip::entry("UnionFind"))]
+#[cfg_attr(nightly, codesnip::entry("BinaryIndexedTree2D", include("Group")))]
+pub mod binary_indexed_tree_2d;
+
+#[cfg_attr(nightly, codesnip::entry("FoldableQueue", include("Monoid")))]
+pub mod foldable_queue;
+
+#[cfg_attr(nightly, codesnip::entry("UnionFind", include("Semigroup", "UnitSemigroup")))]
 pub mod union_find;
 
+#[cfg_attr(nightly, codesnip::entry("RollbackUnionFind"))]
+pub mod rollback_union_find;
+
+#[cfg_attr(nightly, codesnip::entry("WeightedUnionFind", include("Group")))]
+pub mod weighted_union_find;
+
 pub mod segment_tree;
+
+#[cfg_attr(nightly, codesnip::entry("ImplicitTreap", include("LazySegTree")))]
+pub mod treap;
+
+#[cfg_attr(nightly, codesnip::entry("SparseTable", include("Idempotent")))]
+pub mod sparse_table;
+
+#[cfg_attr(
+    nightly,
+    codesnip::entry("WaveletMatrix", include("BinaryIndexedTree", "AddMonoid"))
+)]
+pub mod wavelet_matrix;
+
+#[cfg_attr(nightly, codesnip::entry("LiChaoTree", include("MinMax")))]
+pub mod li_chao_tree;
+
+#[cfg_attr(nightly, codesnip::entry("ConvexHullTrick", include("MinMax")))]
+pub mod convex_hull_trick;
+
+pub mod graph;
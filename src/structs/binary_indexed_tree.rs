@@ -1,6 +1,11 @@
 use super::Monoid;
+use crate::math::num::{alge_struct::types::AddGroup, monoid_pow, Group, Zero};
 
-use std::ops::{RangeFull, RangeTo, RangeToInclusive};
+use std::ops::{
+    Add,
+    Bound::{Excluded, Included, Unbounded},
+    Neg, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
 
 /// A data structure that can efficiently operate elements and calculate prefix folds in a vec.
 ///
@@ -61,7 +66,8 @@ impl<T: Monoid> BinaryIndexedTree<T> {
     }
 
     /// Returns a folded value.
-    /// The `index` is allowed with [`RangeTo`], [`RangeToInclusive`] or [`RangeFull`].
+    /// The `index` is allowed with [`RangeTo`], [`RangeToInclusive`] or [`RangeFull`],
+    /// and also with [`Range`], [`RangeInclusive`] or [`RangeFrom`] if `T` is a [`Group`].
     ///
     /// This operation is O(log(*n*)).
     ///
@@ -80,6 +86,16 @@ impl<T: Monoid> BinaryIndexedTree<T> {
     /// assert_eq!(bit.fold(..=2), 6);
     /// assert_eq!(bit.fold(..), 10);
     /// ```
+    ///
+    /// ```
+    /// # use lib_rust::structs::binary_indexed_tree::BinaryIndexedTree;
+    /// use lib_rust::math::num::alge_struct::types::AddGroup;
+    ///
+    /// let bit = BinaryIndexedTree::<AddGroup<_>>::from(vec![1, 2, 3, 4]);
+    /// assert_eq!(bit.fold(1..3), 5);
+    /// assert_eq!(bit.fold(1..=3), 9);
+    /// assert_eq!(bit.fold(2..), 7);
+    /// ```
     pub fn fold<I: BITIndex<T>>(&self, index: I) -> T::Set {
         index.fold(self)
     }
@@ -107,6 +123,71 @@ impl<T: Monoid> BinaryIndexedTree<T> {
             i += lsb(i + 1);
         }
     }
+
+    /// Returns the smallest `i` such that `pred(&self.fold(..=i))` is `true`,
+    /// or `self.len()` if there is no such `i`.
+    ///
+    /// `pred` must be monotone, i.e. once it returns `true` for a prefix,
+    /// it returns `true` for every longer prefix.
+    ///
+    /// This operation is O(log(*n*)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use lib_rust::structs::binary_indexed_tree::BinaryIndexedTree;
+    /// use lib_rust::math::num::alge_struct::types::AddMonoid;
+    ///
+    /// let bit = BinaryIndexedTree::<AddMonoid<_>>::from(vec![1, 2, 3, 4]);
+    /// assert_eq!(bit.lower_bound(|&sum| sum >= 3), 1);
+    /// assert_eq!(bit.lower_bound(|&sum| sum > 3), 2);
+    /// assert_eq!(bit.lower_bound(|&sum| sum > 10), 4);
+    /// ```
+    pub fn lower_bound<F: FnMut(&T::Set) -> bool>(&self, mut pred: F) -> usize {
+        let mut pos = 0;
+        let mut acc = T::id();
+        let mut k = self.len().next_power_of_two();
+        while k > 0 {
+            if pos + k <= self.len() {
+                let next = T::operate(&acc, &self.tree[pos + k - 1]);
+                if !pred(&next) {
+                    pos += k;
+                    acc = next;
+                }
+            }
+            k >>= 1;
+        }
+        pos
+    }
+}
+
+impl<T: Group> BinaryIndexedTree<T> {
+    /// Returns the value at `index`.
+    ///
+    /// This operation is O(log(*n*)).
+    pub fn get(&self, index: usize) -> T::Set {
+        self.fold(index..=index)
+    }
+
+    /// Sets `value` at `index`.
+    ///
+    /// This operation is O(log(*n*)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use lib_rust::structs::binary_indexed_tree::BinaryIndexedTree;
+    /// use lib_rust::math::num::alge_struct::types::AddGroup;
+    ///
+    /// let mut bit = BinaryIndexedTree::<AddGroup<_>>::from(vec![1, 2, 3, 4]);
+    /// bit.set(2, 10);
+    /// assert_eq!(bit.get(2), 10);
+    /// assert_eq!(bit.fold(..), 17);
+    /// ```
+    pub fn set(&mut self, index: usize, value: T::Set) {
+        let diff = T::inv_operate(&value, &self.get(index));
+        self.operate(index, &diff);
+    }
 }
 
 pub trait BITIndex<T: Monoid> {
@@ -139,6 +220,25 @@ impl<T: Monoid> BITIndex<T> for RangeFull {
     }
 }
 
+impl<T: Group> BITIndex<T> for Range<usize> {
+    fn fold(self, bit: &BinaryIndexedTree<T>) -> T::Set {
+        assert!(self.start <= self.end);
+        T::operate(&T::inv(&(..self.start).fold(bit)), &(..self.end).fold(bit))
+    }
+}
+
+impl<T: Group> BITIndex<T> for RangeInclusive<usize> {
+    fn fold(self, bit: &BinaryIndexedTree<T>) -> T::Set {
+        BITIndex::fold(*self.start()..*self.end() + 1, bit)
+    }
+}
+
+impl<T: Group> BITIndex<T> for RangeFrom<usize> {
+    fn fold(self, bit: &BinaryIndexedTree<T>) -> T::Set {
+        BITIndex::fold(self.start..bit.len(), bit)
+    }
+}
+
 impl<T: Monoid> From<Vec<T::Set>> for BinaryIndexedTree<T> {
     fn from(v: Vec<T::Set>) -> Self {
         let mut ret = Self::with_size(v.len());
@@ -148,3 +248,173 @@ impl<T: Monoid> From<Vec<T::Set>> for BinaryIndexedTree<T> {
         ret
     }
 }
+
+/// A pair of [`BinaryIndexedTree`]s supporting range add and range sum.
+///
+/// ```
+/// use lib_rust::structs::binary_indexed_tree::RangeAddBIT;
+///
+/// let mut bit = RangeAddBIT::from(vec![1, 2, 3, 4]);
+/// bit.add(1..3, 10);
+/// assert_eq!(bit.sum(..), 30);
+/// assert_eq!(bit.sum(2..), 17);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm | Average     | Worst case  |
+/// | --------- | ----------- | ----------- |
+/// | Memory    | O(*n*)      | O(*n*)      |
+/// | [`add`]   | O(log(*n*)) | O(log(*n*)) |
+/// | [`sum`]   | O(log(*n*)) | O(log(*n*)) |
+///
+/// [`add`]: RangeAddBIT::add
+/// [`sum`]: RangeAddBIT::sum
+pub struct RangeAddBIT<T>
+where
+    T: Clone + Zero + Add<Output = T> + Neg<Output = T>,
+{
+    n: usize,
+    // the prefix sum of `..i` is `mul.fold(..i) * i + add.fold(..i)`
+    mul: BinaryIndexedTree<AddGroup<T>>,
+    add: BinaryIndexedTree<AddGroup<T>>,
+}
+
+impl<T> RangeAddBIT<T>
+where
+    T: Clone + Zero + Add<Output = T> + Neg<Output = T>,
+{
+    /// Creates an initialized tree with `T::zero()`.
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            n: size,
+            mul: BinaryIndexedTree::with_size(size + 1),
+            add: BinaryIndexedTree::with_size(size + 1),
+        }
+    }
+
+    /// Returns the number of elements in the tree.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn range<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.n,
+        };
+        assert!(l <= r);
+        assert!(r <= self.n);
+        (l, r)
+    }
+
+    fn prefix_sum(&self, i: usize) -> T {
+        monoid_pow::<AddGroup<T>>(&self.mul.fold(..i), i) + self.add.fold(..i)
+    }
+
+    /// Adds `x` to each element in `range`.
+    ///
+    /// This operation is O(log(*n*)).
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = self.range(range);
+        self.mul.operate(l, &x);
+        self.mul.operate(r, &-x.clone());
+        self.add.operate(l, &-monoid_pow::<AddGroup<T>>(&x, l));
+        self.add.operate(r, &monoid_pow::<AddGroup<T>>(&x, r));
+    }
+
+    /// Returns the sum of `range`.
+    ///
+    /// This operation is O(log(*n*)).
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let (l, r) = self.range(range);
+        self.prefix_sum(r) + -self.prefix_sum(l)
+    }
+}
+
+impl<T> From<Vec<T>> for RangeAddBIT<T>
+where
+    T: Clone + Zero + Add<Output = T> + Neg<Output = T>,
+{
+    fn from(v: Vec<T>) -> Self {
+        let mut ret = Self::with_size(v.len());
+        v.into_iter()
+            .enumerate()
+            .for_each(|(i, x)| ret.add(i..=i, x));
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryIndexedTree, RangeAddBIT};
+    use crate::math::num::alge_struct::types::{AddGroup, AddMonoid, BitXorGroup};
+
+    #[test]
+    fn group_fold() {
+        let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let n = v.len();
+        let mut bit = BinaryIndexedTree::<AddGroup<i64>>::from(v.clone());
+        let mut xor = BinaryIndexedTree::<BitXorGroup<i64>>::from(v.clone());
+        for t in 0..n {
+            v[t * 4 % n] = t as i64 - 3;
+            bit.set(t * 4 % n, t as i64 - 3);
+            xor.set(t * 4 % n, t as i64 - 3);
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!(bit.fold(l..r), v[l..r].iter().sum::<i64>());
+                    assert_eq!(xor.fold(l..r), v[l..r].iter().fold(0, |a, b| a ^ b));
+                }
+                if l < n {
+                    assert_eq!(bit.fold(l..=l), v[l]);
+                    assert_eq!(bit.get(l), v[l]);
+                }
+                assert_eq!(bit.fold(l..), v[l..].iter().sum::<i64>());
+            }
+        }
+    }
+
+    #[test]
+    fn lower_bound() {
+        for n in 0..20 {
+            let v = (0..n).map(|i| (i * 7 % 5) as u32).collect::<Vec<_>>();
+            let bit = BinaryIndexedTree::<AddMonoid<u32>>::from(v.clone());
+            for x in 0..=v.iter().sum::<u32>() + 1 {
+                let expected = (0..n)
+                    .find(|&i| v[..=i].iter().sum::<u32>() >= x)
+                    .unwrap_or(n);
+                assert_eq!(bit.lower_bound(|&sum| sum >= x), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn range_add() {
+        let mut v = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 5, 9];
+        let n = v.len();
+        let mut bit = RangeAddBIT::from(v.clone());
+        for t in 0..50 {
+            let (l, r) = (t * 5 % n, t * 11 % (n + 1));
+            let (l, r) = (l.min(r), l.max(r));
+            let x = t as i64 % 7 - 3;
+            bit.add(l..r, x);
+            v[l..r].iter_mut().for_each(|a| *a += x);
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!(bit.sum(l..r), v[l..r].iter().sum::<i64>());
+                }
+            }
+            assert_eq!(bit.sum(..), v.iter().sum::<i64>());
+        }
+    }
+}
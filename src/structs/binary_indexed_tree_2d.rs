@@ -0,0 +1,206 @@
+use crate::math::num::{Group, Monoid};
+
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+/// A two-dimensional [`BinaryIndexedTree`].
+///
+/// `T` must be commutative since the order of folding is not specified.
+///
+/// [`BinaryIndexedTree`]: super::binary_indexed_tree::BinaryIndexedTree
+///
+/// ```
+/// use lib_rust::structs::binary_indexed_tree_2d::BinaryIndexedTree2D;
+/// use lib_rust::math::num::alge_struct::types::AddGroup;
+///
+/// let mut bit = BinaryIndexedTree2D::<AddGroup<_>>::from(vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ]);
+/// bit.operate(0, 1, &10);
+/// assert_eq!(bit.prefix_fold(1, 2), 13);
+/// assert_eq!(bit.fold(.., 1..), 26);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm       | Average                | Worst case             |
+/// | --------------- | ---------------------- | ---------------------- |
+/// | Memory          | O(*hw*)                | O(*hw*)                |
+/// | [`prefix_fold`] | O(log(*h*) log(*w*))   | O(log(*h*) log(*w*))   |
+/// | [`fold`]        | O(log(*h*) log(*w*))   | O(log(*h*) log(*w*))   |
+/// | [`operate`]     | O(log(*h*) log(*w*))   | O(log(*h*) log(*w*))   |
+///
+/// [`prefix_fold`]: BinaryIndexedTree2D::prefix_fold
+/// [`fold`]: BinaryIndexedTree2D::fold
+/// [`operate`]: BinaryIndexedTree2D::operate
+pub struct BinaryIndexedTree2D<T: Monoid> {
+    h: usize,
+    w: usize,
+    tree: Vec<T::Set>,
+}
+
+/// Type alias for [`BinaryIndexedTree2D`].
+pub type BIT2D<T> = BinaryIndexedTree2D<T>;
+
+/// Returns the least significant bit by `i`.
+fn lsb(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl<T: Monoid> BinaryIndexedTree2D<T> {
+    /// Creates an initialized `h` x `w` tree with `Monoid::id()`.
+    pub fn with_size(h: usize, w: usize) -> Self {
+        Self {
+            h,
+            w,
+            tree: vec![T::id(); h * w],
+        }
+    }
+
+    /// Returns the number of rows and columns.
+    pub fn size(&self) -> (usize, usize) {
+        (self.h, self.w)
+    }
+
+    /// Returns the fold of the rectangle `..i` x `..j`.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the rectangle is out of bounds.
+    pub fn prefix_fold(&self, i: usize, j: usize) -> T::Set {
+        assert!(i <= self.h && j <= self.w);
+        let mut ret = T::id();
+        let mut i = i;
+        while 0 < i {
+            let mut j = j;
+            while 0 < j {
+                ret = T::operate(&self.tree[(i - 1) * self.w + j - 1], &ret);
+                j -= lsb(j);
+            }
+            i -= lsb(i);
+        }
+        ret
+    }
+
+    /// Update the value at (`i`, `j`) with [`Semigroup::operate`].
+    ///
+    /// [`Semigroup::operate`]: crate::math::num::Semigroup
+    pub fn operate(&mut self, i: usize, j: usize, value: &T::Set) {
+        assert!(i < self.h && j < self.w);
+        let mut i = i;
+        while i < self.h {
+            let mut j = j;
+            while j < self.w {
+                let k = i * self.w + j;
+                self.tree[k] = T::operate(value, &self.tree[k]);
+                j += lsb(j + 1);
+            }
+            i += lsb(i + 1);
+        }
+    }
+}
+
+impl<T: Group> BinaryIndexedTree2D<T> {
+    /// Returns the fold of the rectangle `rows` x `cols`.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the rectangle is out of bounds.
+    pub fn fold<R, C>(&self, rows: R, cols: C) -> T::Set
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (il, ir) = to_range(&rows, self.h);
+        let (jl, jr) = to_range(&cols, self.w);
+        let outer = T::operate(&self.prefix_fold(ir, jr), &self.prefix_fold(il, jl));
+        let inner = T::operate(&self.prefix_fold(il, jr), &self.prefix_fold(ir, jl));
+        T::inv_operate(&outer, &inner)
+    }
+}
+
+fn to_range<R: RangeBounds<usize>>(range: &R, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l + 1,
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r + 1,
+        Excluded(&r) => r,
+        Unbounded => n,
+    };
+    assert!(l <= r);
+    (l, r)
+}
+
+impl<T: Monoid> From<Vec<Vec<T::Set>>> for BinaryIndexedTree2D<T> {
+    fn from(v: Vec<Vec<T::Set>>) -> Self {
+        let h = v.len();
+        let w = v.first().map_or(0, Vec::len);
+        let mut ret = Self::with_size(h, w);
+        for (i, row) in v.into_iter().enumerate() {
+            assert_eq!(row.len(), w);
+            row.into_iter()
+                .enumerate()
+                .for_each(|(j, x)| ret.operate(i, j, &x));
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryIndexedTree2D;
+    use crate::math::num::alge_struct::types::{AddGroup, BitXorGroup, MaxMonoid};
+
+    #[test]
+    fn fold() {
+        let (h, w) = (6, 5);
+        let mut v = vec![vec![0_i64; w]; h];
+        let mut v_xor = vec![vec![0_u32; w]; h];
+        let mut v_max = vec![vec![core::i64::MIN; w]; h];
+        let mut sum = BinaryIndexedTree2D::<AddGroup<i64>>::with_size(h, w);
+        let mut xor = BinaryIndexedTree2D::<BitXorGroup<u32>>::with_size(h, w);
+        let mut max = BinaryIndexedTree2D::<MaxMonoid<i64>>::with_size(h, w);
+        for t in 0..30 {
+            let (i, j, x) = (t * 5 % h, t * 3 % w, (t * 7 % 19) as i64 - 9);
+            v[i][j] += x;
+            v_xor[i][j] ^= x as u32;
+            v_max[i][j] = v_max[i][j].max(x);
+            sum.operate(i, j, &x);
+            xor.operate(i, j, &(x as u32));
+            max.operate(i, j, &x);
+            for il in 0..=h {
+                for ir in il..=h {
+                    for jl in 0..=w {
+                        for jr in jl..=w {
+                            let cells = v[il..ir].iter().flat_map(|row| &row[jl..jr]);
+                            assert_eq!(sum.fold(il..ir, jl..jr), cells.sum::<i64>());
+                            let cells = v_xor[il..ir].iter().flat_map(|row| &row[jl..jr]);
+                            assert_eq!(xor.fold(il..ir, jl..jr), cells.fold(0, |a, &b| a ^ b));
+                        }
+                    }
+                }
+            }
+            for i in 0..=h {
+                for j in 0..=w {
+                    let cells = v_max[..i].iter().flat_map(|row| &row[..j]);
+                    assert_eq!(
+                        max.prefix_fold(i, j),
+                        cells.copied().max().unwrap_or(core::i64::MIN)
+                    );
+                }
+            }
+        }
+        let bit = BinaryIndexedTree2D::<AddGroup<i64>>::from(v.clone());
+        for i in 0..=h {
+            for j in 0..=w {
+                assert_eq!(bit.prefix_fold(i, j), sum.prefix_fold(i, j));
+            }
+        }
+    }
+}
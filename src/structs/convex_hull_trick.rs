@@ -0,0 +1,216 @@
+use crate::math::num::alge_struct::types::MinMax;
+
+use core::marker::PhantomData;
+use std::collections::VecDeque;
+
+/// A lower (or upper) envelope of lines added in the order of the slopes,
+/// answering the minimum (or maximum) of the lines at a point.
+///
+/// `M` is [`MinMonoid<i64>`] to take the minimum or [`MaxMonoid<i64>`] to take the maximum.
+/// The slopes must be added in the order they win for large `x`,
+/// i.e. non-increasing for the minimum and non-decreasing for the maximum.
+/// `a * x + b` must fit in `i64` for all the lines and queries.
+/// For the maximum, the lines are negated inside,
+/// so `a` and `b` must not be `i64::MIN` and `-a * x - b` must fit in `i64` as well.
+///
+/// [`MinMonoid<i64>`]: crate::math::num::alge_struct::types::MinMonoid
+/// [`MaxMonoid<i64>`]: crate::math::num::alge_struct::types::MaxMonoid
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::convex_hull_trick::ConvexHullTrick;
+/// use lib_rust::math::num::alge_struct::types::MinMonoid;
+///
+/// let mut cht = ConvexHullTrick::<MinMonoid<i64>>::new();
+/// cht.add_line(2, 0);
+/// cht.add_line(0, 3);
+/// cht.add_line(-1, 8);
+/// assert_eq!(cht.query(1), 2);
+/// assert_eq!(cht.query(7), 1);
+/// assert_eq!(cht.query_monotone(0), 0);
+/// assert_eq!(cht.query_monotone(3), 3);
+/// assert_eq!(cht.query_monotone(10), -2);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm          | Amortized   |
+/// | ------------------ | ----------- |
+/// | [`add_line`]       | O(1)        |
+/// | [`query`]          | O(log(*n*)) |
+/// | [`query_monotone`] | O(1)        |
+///
+/// [`add_line`]: ConvexHullTrick::add_line
+/// [`query`]: ConvexHullTrick::query
+/// [`query_monotone`]: ConvexHullTrick::query_monotone
+pub struct ConvexHullTrick<M> {
+    // the lines on the envelope in the order of the slopes,
+    // negated for the maximum so that this always keeps the lower envelope
+    lines: VecDeque<(i64, i64)>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: MinMax<Set = i64>> ConvexHullTrick<M> {
+    pub fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns `1` for the minimum and `-1` for the maximum.
+    fn sign() -> i64 {
+        if M::IS_MIN {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Returns whether `mid` is unnecessary between `left` and `right`,
+    /// where their slopes are decreasing.
+    fn is_redundant(left: (i64, i64), mid: (i64, i64), right: (i64, i64)) -> bool {
+        let (a1, b1) = (left.0 as i128, left.1 as i128);
+        let (a2, b2) = (mid.0 as i128, mid.1 as i128);
+        let (a3, b3) = (right.0 as i128, right.1 as i128);
+        (b3 - b1) * (a1 - a2) <= (b2 - b1) * (a1 - a3)
+    }
+
+    /// Adds a line `y = a * x + b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` does not follow the order of the slopes,
+    /// or `a` or `b` is `i64::MIN` for the maximum.
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        let negate = |v: i64| {
+            v.checked_neg()
+                .expect("`i64::MIN` cannot be negated for the maximum")
+        };
+        let (a, b) = if M::IS_MIN {
+            (a, b)
+        } else {
+            (negate(a), negate(b))
+        };
+        if let Some(&(last_a, last_b)) = self.lines.back() {
+            assert!(a <= last_a, "the slopes must be added in order");
+            if a == last_a {
+                if b >= last_b {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if Self::is_redundant(self.lines[n - 2], self.lines[n - 1], (a, b)) {
+                self.lines.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.lines.push_back((a, b));
+    }
+
+    fn eval(&self, i: usize, x: i64) -> i64 {
+        let (a, b) = self.lines[i];
+        a * x + b
+    }
+
+    /// Returns the minimum or maximum of the lines at `x`, or `M::id()` if there is no line.
+    pub fn query(&self, x: i64) -> i64 {
+        if self.lines.is_empty() {
+            return M::id();
+        }
+        // the first line not worse than the next one
+        let (mut l, mut r) = (0, self.lines.len() - 1);
+        while l < r {
+            let m = (l + r) / 2;
+            if self.eval(m, x) >= self.eval(m + 1, x) {
+                l = m + 1;
+            } else {
+                r = m;
+            }
+        }
+        self.eval(l, x) * Self::sign()
+    }
+
+    /// Returns the same as [`query`](Self::query) in amortized O(1),
+    /// discarding the lines which never win at `x` or later.
+    ///
+    /// `x` must be non-decreasing through the calls, and [`query`](Self::query) must not be
+    /// called with a smaller `x` after this.
+    pub fn query_monotone(&mut self, x: i64) -> i64 {
+        if self.lines.is_empty() {
+            return M::id();
+        }
+        while self.lines.len() >= 2 && self.eval(0, x) >= self.eval(1, x) {
+            self.lines.pop_front();
+        }
+        self.eval(0, x) * Self::sign()
+    }
+}
+
+impl<M: MinMax<Set = i64>> Default for ConvexHullTrick<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConvexHullTrick;
+    use crate::math::num::alge_struct::types::{MaxMonoid, MinMonoid};
+
+    #[test]
+    fn query() {
+        let mut lines = (0..80_i64)
+            .map(|i| (i * 13 % 41 - 20, i * 29 % 97 - 48))
+            .collect::<Vec<_>>();
+        lines.sort_by_key(|&(a, _)| -a);
+        let mut min = ConvexHullTrick::<MinMonoid<i64>>::new();
+        let mut max = ConvexHullTrick::<MaxMonoid<i64>>::new();
+        for (i, &(a, b)) in lines.iter().enumerate() {
+            min.add_line(a, b);
+            max.add_line(-a, b);
+            for x in -30..30 {
+                let values = lines[..=i].iter().map(|&(a, b)| a * x + b);
+                assert_eq!(min.query(x), values.clone().min().unwrap());
+                let values = lines[..=i].iter().map(|&(a, b)| -a * x + b);
+                assert_eq!(max.query(x), values.max().unwrap());
+            }
+        }
+        for x in -30..30 {
+            let values = lines.iter().map(|&(a, b)| a * x + b);
+            assert_eq!(min.query_monotone(x), values.clone().min().unwrap());
+            let values = lines.iter().map(|&(a, b)| -a * x + b);
+            assert_eq!(max.query_monotone(x), values.max().unwrap());
+        }
+        let values = lines.iter().map(|&(a, b)| a * 100 + b);
+        assert_eq!(min.query_monotone(100), values.min().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn negate_min_value() {
+        let mut cht = ConvexHullTrick::<MaxMonoid<i64>>::new();
+        cht.add_line(0, core::i64::MIN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unordered_slopes() {
+        let mut cht = ConvexHullTrick::<MaxMonoid<i64>>::new();
+        cht.add_line(1, 0);
+        cht.add_line(0, 0);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::math::num::Monoid;
+
+/// A queue which folds all the elements with a [`Monoid`] in O(1).
+///
+/// The elements are folded from the front to the back,
+/// so `M` does not need to be commutative.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::foldable_queue::FoldableQueue;
+/// use lib_rust::math::num::alge_struct::types::MaxMonoid;
+///
+/// let mut queue = FoldableQueue::<MaxMonoid<i32>>::new();
+/// let mut window_max = vec![];
+/// for &x in &[3, 1, 4, 1, 5, 9, 2, 6] {
+///     queue.push_back(x);
+///     if queue.len() > 3 {
+///         queue.pop_front();
+///     }
+///     window_max.push(queue.fold());
+/// }
+/// assert_eq!(window_max, vec![3, 3, 4, 4, 5, 9, 9, 9]);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm     | Amortized |
+/// | ------------- | --------- |
+/// | [`push_back`] | O(1)      |
+/// | [`pop_front`] | O(1)      |
+/// | [`fold`]      | O(1)      |
+///
+/// [`push_back`]: FoldableQueue::push_back
+/// [`pop_front`]: FoldableQueue::pop_front
+/// [`fold`]: FoldableQueue::fold
+pub struct FoldableQueue<M: Monoid> {
+    // the front elements with the folds from them to the back of `front`,
+    // where the last one is the front of the queue
+    front: Vec<(M::Set, M::Set)>,
+    back: Vec<M::Set>,
+    back_fold: M::Set,
+}
+
+impl<M: Monoid> FoldableQueue<M> {
+    pub fn new() -> Self {
+        Self {
+            front: vec![],
+            back: vec![],
+            back_fold: M::id(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_back(&mut self, value: M::Set) {
+        self.back_fold = M::operate(&self.back_fold, &value);
+        self.back.push(value);
+    }
+
+    pub fn pop_front(&mut self) -> Option<M::Set> {
+        if self.front.is_empty() {
+            let mut fold = M::id();
+            while let Some(value) = self.back.pop() {
+                fold = M::operate(&value, &fold);
+                self.front.push((value, fold.clone()));
+            }
+            self.back_fold = M::id();
+        }
+        self.front.pop().map(|(value, _)| value)
+    }
+
+    pub fn front(&self) -> Option<&M::Set> {
+        self.front
+            .last()
+            .map(|(value, _)| value)
+            .or_else(|| self.back.first())
+    }
+
+    /// Returns the fold of all the elements from the front to the back.
+    pub fn fold(&self) -> M::Set {
+        match self.front.last() {
+            Some((_, fold)) => M::operate(fold, &self.back_fold),
+            None => self.back_fold.clone(),
+        }
+    }
+}
+
+impl<M: Monoid> Default for FoldableQueue<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A deque which folds all the elements with a [`Monoid`] in O(1).
+///
+/// The elements are folded from the front to the back,
+/// so `M` does not need to be commutative.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::foldable_queue::FoldableDeque;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let mut deque = FoldableDeque::<AddMonoid<i32>>::new();
+/// deque.push_back(1);
+/// deque.push_front(2);
+/// deque.push_back(3);
+/// assert_eq!(deque.fold(), 6);
+/// assert_eq!(deque.pop_back(), Some(3));
+/// assert_eq!(deque.pop_back(), Some(1));
+/// assert_eq!(deque.fold(), 2);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm      | Amortized |
+/// | -------------- | --------- |
+/// | [`push_front`] | O(1)      |
+/// | [`push_back`]  | O(1)      |
+/// | [`pop_front`]  | O(1)      |
+/// | [`pop_back`]   | O(1)      |
+/// | [`fold`]       | O(1)      |
+///
+/// [`push_front`]: FoldableDeque::push_front
+/// [`push_back`]: FoldableDeque::push_back
+/// [`pop_front`]: FoldableDeque::pop_front
+/// [`pop_back`]: FoldableDeque::pop_back
+/// [`fold`]: FoldableDeque::fold
+pub struct FoldableDeque<M: Monoid> {
+    // the last one is the front of the deque
+    front: Vec<(M::Set, M::Set)>,
+    // the last one is the back of the deque
+    back: Vec<(M::Set, M::Set)>,
+}
+
+impl<M: Monoid> FoldableDeque<M> {
+    pub fn new() -> Self {
+        Self {
+            front: vec![],
+            back: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_front(&mut self, value: M::Set) {
+        let fold = match self.front.last() {
+            Some((_, fold)) => M::operate(&value, fold),
+            None => value.clone(),
+        };
+        self.front.push((value, fold));
+    }
+
+    pub fn push_back(&mut self, value: M::Set) {
+        let fold = match self.back.last() {
+            Some((_, fold)) => M::operate(fold, &value),
+            None => value.clone(),
+        };
+        self.back.push((value, fold));
+    }
+
+    /// Splits the elements into `front` and `back` so that `front` has `mid` elements.
+    fn rebuild(&mut self, mid: usize) {
+        let mut values = self
+            .front
+            .drain(..)
+            .rev()
+            .chain(self.back.drain(..))
+            .map(|(value, _)| value)
+            .collect::<Vec<_>>();
+        let back = values.split_off(mid);
+        values.into_iter().rev().for_each(|x| self.push_front(x));
+        back.into_iter().for_each(|x| self.push_back(x));
+    }
+
+    pub fn pop_front(&mut self) -> Option<M::Set> {
+        if self.front.is_empty() {
+            self.rebuild((self.len() + 1) / 2);
+        }
+        self.front.pop().map(|(value, _)| value)
+    }
+
+    pub fn pop_back(&mut self) -> Option<M::Set> {
+        if self.back.is_empty() {
+            self.rebuild(self.len() / 2);
+        }
+        self.back.pop().map(|(value, _)| value)
+    }
+
+    pub fn front(&self) -> Option<&M::Set> {
+        self.front
+            .last()
+            .or_else(|| self.back.first())
+            .map(|(value, _)| value)
+    }
+
+    pub fn back(&self) -> Option<&M::Set> {
+        self.back
+            .last()
+            .or_else(|| self.front.first())
+            .map(|(value, _)| value)
+    }
+
+    /// Returns the fold of all the elements from the front to the back.
+    pub fn fold(&self) -> M::Set {
+        match (self.front.last(), self.back.last()) {
+            (Some((_, lhs)), Some((_, rhs))) => M::operate(lhs, rhs),
+            (Some((_, fold)), None) | (None, Some((_, fold))) => fold.clone(),
+            (None, None) => M::id(),
+        }
+    }
+}
+
+impl<M: Monoid> Default for FoldableDeque<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FoldableDeque, FoldableQueue};
+    use crate::math::num::{Monoid, Semigroup};
+    use std::collections::VecDeque;
+
+    const MOD: u64 = 998_244_353;
+
+    /// Composition of `x -> ax + b`, applying the left one first.
+    struct Affine;
+    impl Semigroup for Affine {
+        type Set = (u64, u64);
+        fn operate(&(a, b): &Self::Set, &(c, d): &Self::Set) -> Self::Set {
+            (a * c % MOD, (b * c + d) % MOD)
+        }
+    }
+    impl Monoid for Affine {
+        fn id() -> Self::Set {
+            (1, 0)
+        }
+    }
+
+    fn brute<'a, I: Iterator<Item = &'a (u64, u64)>>(iter: I) -> (u64, u64) {
+        iter.fold(Affine::id(), |acc, x| Affine::operate(&acc, x))
+    }
+
+    #[test]
+    fn queue() {
+        let mut queue = FoldableQueue::<Affine>::new();
+        let mut expected = VecDeque::new();
+        for i in 0..200_u64 {
+            if i % 3 == 2 {
+                assert_eq!(queue.pop_front(), expected.pop_front());
+            } else {
+                let x = (i * 7 % 11 + 1, i * 13 % 17);
+                queue.push_back(x);
+                expected.push_back(x);
+            }
+            assert_eq!(queue.len(), expected.len());
+            assert_eq!(queue.front(), expected.front());
+            assert_eq!(queue.fold(), brute(expected.iter()));
+        }
+        while !expected.is_empty() {
+            assert_eq!(queue.pop_front(), expected.pop_front());
+        }
+        assert_eq!(queue.pop_front(), None);
+        assert_eq!(queue.fold(), Affine::id());
+    }
+
+    #[test]
+    fn deque() {
+        let mut deque = FoldableDeque::<Affine>::new();
+        let mut expected = VecDeque::new();
+        for i in 0..500_u64 {
+            let x = (i * 7 % 11 + 1, i * 13 % 17);
+            match (i * 3 + i / 11) % 7 {
+                0 | 1 => {
+                    deque.push_back(x);
+                    expected.push_back(x);
+                }
+                2 | 3 => {
+                    deque.push_front(x);
+                    expected.push_front(x);
+                }
+                4 | 5 => assert_eq!(deque.pop_front(), expected.pop_front()),
+                _ => assert_eq!(deque.pop_back(), expected.pop_back()),
+            }
+            assert_eq!(deque.len(), expected.len());
+            assert_eq!(deque.front(), expected.front());
+            assert_eq!(deque.back(), expected.back());
+            assert_eq!(deque.fold(), brute(expected.iter()));
+        }
+    }
+}
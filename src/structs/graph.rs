@@ -0,0 +1,34 @@
+#[cfg_attr(nightly, codesnip::entry("Graph"))]
+mod csr;
+#[codesnip::entry("Graph")]
+pub use csr::{Edge, Graph, IntoEdge};
+
+#[cfg_attr(nightly, codesnip::entry("ShortestPath", include("Graph", "Zero")))]
+mod shortest_path;
+#[codesnip::entry("ShortestPath")]
+pub use shortest_path::{
+    bellman_ford, bfs, bfs01, dijkstra, floyd_warshall, AllPairsShortestPaths, ShortestPaths,
+};
+
+#[cfg_attr(nightly, codesnip::entry("Grid", include("dir4", "dir8")))]
+mod grid;
+#[codesnip::entry("Grid")]
+pub use grid::Grid;
+
+#[cfg_attr(nightly, codesnip::entry("MST", include("Graph", "UnionFind", "Zero")))]
+mod mst;
+#[codesnip::entry("MST")]
+pub use mst::{boruvka, kruskal, minimum_spanning_forest, prim, SpanningTree};
+
+#[cfg_attr(nightly, codesnip::entry("SCC", include("Graph")))]
+mod scc;
+#[codesnip::entry("SCC")]
+pub use scc::{StronglyConnectedComponents, TwoSat, SCC};
+
+#[cfg_attr(
+    nightly,
+    codesnip::entry("TopologicalSort", include("Graph", "Semigroup"))
+)]
+mod topological;
+#[codesnip::entry("TopologicalSort")]
+pub use topological::{dag_dp, lexicographic_topological_sort, topological_sort};
@@ -0,0 +1,203 @@
+/// An edge of a [`Graph`].
+///
+/// For an undirected graph, the adjacency of `to` has the same edge with `from` and `to` swapped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+    /// The index in the order of the edges given at the construction.
+    pub id: usize,
+}
+
+/// Conversion into `(from, to, weight)`, so that an unweighted graph can be built from `(from, to)`.
+pub trait IntoEdge<W> {
+    fn into_edge(self) -> (usize, usize, W);
+}
+
+impl<W> IntoEdge<W> for (usize, usize, W) {
+    fn into_edge(self) -> (usize, usize, W) {
+        self
+    }
+}
+
+impl IntoEdge<()> for (usize, usize) {
+    fn into_edge(self) -> (usize, usize, ()) {
+        (self.0, self.1, ())
+    }
+}
+
+/// A static graph in the compressed sparse row format.
+///
+/// `W` is the type of the weights, and `()` for an unweighted graph.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::Graph;
+///
+/// let graph = Graph::undirected(4, vec![(0, 1, 5), (1, 2, 3), (1, 3, 1)]);
+/// assert_eq!(graph.len(), 4);
+/// assert_eq!(graph.edge_count(), 3);
+/// let adj = graph.adj(1).iter().map(|e| (e.to, e.weight)).collect::<Vec<_>>();
+/// assert_eq!(adj, vec![(0, 5), (2, 3), (3, 1)]);
+///
+/// let graph = Graph::directed(3, vec![(0, 1), (0, 2), (2, 1)]);
+/// assert_eq!(graph.adj(0).len(), 2);
+/// assert!(graph.adj(1).is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Graph<W = ()> {
+    directed: bool,
+    edges: Vec<Edge<W>>,
+    // `adj[start[v]..start[v + 1]]` is the adjacency of `v`
+    start: Vec<usize>,
+    adj: Vec<Edge<W>>,
+}
+
+impl<W: Clone> Graph<W> {
+    fn new<E, I>(n: usize, edges: I, directed: bool) -> Self
+    where
+        E: IntoEdge<W>,
+        I: IntoIterator<Item = E>,
+    {
+        let edges = edges
+            .into_iter()
+            .enumerate()
+            .map(|(id, e)| {
+                let (from, to, weight) = e.into_edge();
+                assert!(from < n && to < n);
+                Edge {
+                    from,
+                    to,
+                    weight,
+                    id,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut start = vec![0; n + 1];
+        for e in &edges {
+            start[e.from + 1] += 1;
+            if !directed {
+                start[e.to + 1] += 1;
+            }
+        }
+        for v in 0..n {
+            start[v + 1] += start[v];
+        }
+        let mut pos = start.clone();
+        let mut adj = vec![None; start[n]];
+        for e in &edges {
+            adj[pos[e.from]] = Some(e.clone());
+            pos[e.from] += 1;
+            if !directed {
+                adj[pos[e.to]] = Some(Edge {
+                    from: e.to,
+                    to: e.from,
+                    weight: e.weight.clone(),
+                    id: e.id,
+                });
+                pos[e.to] += 1;
+            }
+        }
+        let adj = adj.into_iter().map(Option::unwrap).collect();
+
+        Self {
+            directed,
+            edges,
+            start,
+            adj,
+        }
+    }
+
+    /// Creates a directed graph with `n` vertices from `(from, to, weight)` or `(from, to)`.
+    pub fn directed<E, I>(n: usize, edges: I) -> Self
+    where
+        E: IntoEdge<W>,
+        I: IntoIterator<Item = E>,
+    {
+        Self::new(n, edges, true)
+    }
+
+    /// Creates an undirected graph with `n` vertices from `(u, v, weight)` or `(u, v)`.
+    pub fn undirected<E, I>(n: usize, edges: I) -> Self
+    where
+        E: IntoEdge<W>,
+        I: IntoIterator<Item = E>,
+    {
+        Self::new(n, edges, false)
+    }
+
+    /// Returns the graph with all the edges reversed.
+    pub fn reverse(&self) -> Self {
+        let edges = self.edges.iter().map(|e| (e.to, e.from, e.weight.clone()));
+        Self::new(self.len(), edges, self.directed)
+    }
+}
+
+impl<W> Graph<W> {
+    /// Returns the number of the vertices.
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the edges in the order given at the construction.
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+
+    /// Returns the edges from `v`.
+    pub fn adj(&self, v: usize) -> &[Edge<W>] {
+        &self.adj[self.start[v]..self.start[v + 1]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn adjacency() {
+        let edges = vec![(0, 1, 'a'), (2, 0, 'b'), (1, 1, 'c'), (0, 3, 'd')];
+        let directed = Graph::directed(5, edges.clone());
+        let undirected = Graph::undirected(5, edges.clone());
+        let adj = |g: &Graph<char>, v| {
+            g.adj(v)
+                .iter()
+                .map(|e| (e.to, e.weight, e.id))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(adj(&directed, 0), vec![(1, 'a', 0), (3, 'd', 3)]);
+        assert_eq!(adj(&directed, 1), vec![(1, 'c', 2)]);
+        assert_eq!(adj(&directed, 4), vec![]);
+        assert_eq!(
+            adj(&undirected, 0),
+            vec![(1, 'a', 0), (2, 'b', 1), (3, 'd', 3)]
+        );
+        assert_eq!(
+            adj(&undirected, 1),
+            vec![(0, 'a', 0), (1, 'c', 2), (1, 'c', 2)]
+        );
+        assert!(undirected.adj(3).iter().all(|e| e.from == 3));
+
+        let reversed = directed.reverse();
+        assert_eq!(adj(&reversed, 0), vec![(2, 'b', 1)]);
+        assert_eq!(reversed.edges()[3].from, 3);
+        assert_eq!(directed.edge_count(), 4);
+        assert_eq!(directed.len(), 5);
+        assert!(directed.is_directed() && !undirected.is_directed());
+    }
+}
@@ -0,0 +1,273 @@
+use core::ops::{Index, IndexMut};
+use std::collections::VecDeque;
+
+/// A grid of cells in the row-major order, with its neighbors and shortest paths.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::Grid;
+///
+/// let grid = Grid::from_strs(&["S.#", "..#", "#.G"]);
+/// let start = grid.position(|&c| c == 'S').unwrap();
+/// let goal = grid.position(|&c| c == 'G').unwrap();
+/// let dist = grid.bfs(start, |&c| c != '#');
+/// assert_eq!(dist[goal.0][goal.1], Some(4));
+/// assert_eq!(dist[0][2], None);
+/// assert_eq!(grid.id(goal), 8);
+/// assert_eq!(grid.pos(8), goal);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// Creates a grid of characters from the rows.
+    pub fn from_strs<I>(rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        rows.into_iter()
+            .map(|row| row.as_ref().chars().collect())
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, (r, c): (usize, usize)) -> Option<&T> {
+        if r < self.height && c < self.width {
+            Some(&self.cells[self.id((r, c))])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of `(r, c)` in the row-major order.
+    pub fn id(&self, (r, c): (usize, usize)) -> usize {
+        r * self.width + c
+    }
+
+    /// Returns the position `(r, c)` of the index in the row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is out of the grid, including any `id` for an empty grid.
+    pub fn pos(&self, id: usize) -> (usize, usize) {
+        assert!(
+            id < self.height * self.width,
+            "the index is out of the grid"
+        );
+        (id / self.width, id % self.width)
+    }
+
+    /// Returns the first position in the row-major order whose cell satisfies `pred`.
+    pub fn position<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<(usize, usize)> {
+        self.cells.iter().position(pred).map(|id| self.pos(id))
+    }
+
+    /// Returns the positions next to `(r, c)` in the 4 directions inside the grid.
+    pub fn neighbors4(&self, (r, c): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        crate::dir4!((r, c) in 0..self.height, 0..self.width)
+    }
+
+    /// Returns the positions next to `(r, c)` in the 8 directions inside the grid.
+    pub fn neighbors8(&self, (r, c): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        crate::dir8!((r, c) in 0..self.height, 0..self.width)
+    }
+
+    /// Returns the distances from `start` moving in the 4 directions
+    /// through the cells satisfying `passable`.
+    ///
+    /// `start` itself has the distance 0 even if it is not passable.
+    pub fn bfs<F>(&self, start: (usize, usize), mut passable: F) -> Vec<Vec<Option<usize>>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut dist = vec![vec![None; self.width]; self.height];
+        let mut queue = VecDeque::new();
+        dist[start.0][start.1] = Some(0);
+        queue.push_back(start);
+        while let Some(p) = queue.pop_front() {
+            let d = dist[p.0][p.1].unwrap();
+            for (r, c) in self.neighbors4(p) {
+                if dist[r][c].is_none() && passable(&self[(r, c)]) {
+                    dist[r][c] = Some(d + 1);
+                    queue.push_back((r, c));
+                }
+            }
+        }
+        dist
+    }
+
+    /// Returns the distances from `start` moving in the 4 directions,
+    /// where `cost` returns the cost 0 or 1 to enter a cell, or `None` if it is not passable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cost` returns more than 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib_rust::structs::graph::Grid;
+    ///
+    /// // the number of walls to break
+    /// let grid = Grid::from_strs(&["..#.", "#.##", "...#"]);
+    /// let dist = grid.bfs01((0, 0), |&c| Some((c == '#') as usize));
+    /// assert_eq!(dist[0][3], Some(1));
+    /// assert_eq!(dist[2][3], Some(1));
+    /// ```
+    pub fn bfs01<F>(&self, start: (usize, usize), mut cost: F) -> Vec<Vec<Option<usize>>>
+    where
+        F: FnMut(&T) -> Option<usize>,
+    {
+        let mut dist = vec![vec![None; self.width]; self.height];
+        let mut deque = VecDeque::new();
+        dist[start.0][start.1] = Some(0);
+        deque.push_back((start, 0));
+        while let Some((p, d)) = deque.pop_front() {
+            if dist[p.0][p.1] != Some(d) {
+                continue;
+            }
+            for (r, c) in self.neighbors4(p) {
+                let w = match cost(&self[(r, c)]) {
+                    Some(w) => w,
+                    None => continue,
+                };
+                assert!(w <= 1, "the cost must be 0 or 1");
+                if dist[r][c].map_or(true, |dist| d + w < dist) {
+                    dist[r][c] = Some(d + w);
+                    if w == 0 {
+                        deque.push_front(((r, c), d));
+                    } else {
+                        deque.push_back(((r, c), d + 1));
+                    }
+                }
+            }
+        }
+        dist
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    /// # Panics
+    ///
+    /// Panics if the rows have different lengths.
+    fn from(v: Vec<Vec<T>>) -> Self {
+        let height = v.len();
+        let width = v.first().map_or(0, Vec::len);
+        assert!(v.iter().all(|row| row.len() == width));
+        let cells = v.into_iter().flatten().collect();
+        Self {
+            height,
+            width,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        assert!(pos.0 < self.height && pos.1 < self.width);
+        &self.cells[self.id(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        assert!(pos.0 < self.height && pos.1 < self.width);
+        let id = self.id(pos);
+        &mut self.cells[id]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::structs::graph::{bfs01, Graph};
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::from(vec![vec![0; 4]; 3]);
+        let mut n4 = grid.neighbors4((0, 3)).collect::<Vec<_>>();
+        n4.sort();
+        assert_eq!(n4, vec![(0, 2), (1, 3)]);
+        let mut n8 = grid.neighbors8((2, 1)).collect::<Vec<_>>();
+        n8.sort();
+        assert_eq!(n8, vec![(1, 0), (1, 1), (1, 2), (2, 0), (2, 2)]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        for id in 0..12 {
+            assert_eq!(grid.id(grid.pos(id)), id);
+        }
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((2, 3)), Some(&0));
+
+        let line = Grid::from(vec![vec![0; 3]]);
+        assert_eq!(line.neighbors8((0, 0)).collect::<Vec<_>>(), vec![(0, 1)]);
+        let empty = Grid::<i32>::from(vec![vec![]; 3]);
+        assert_eq!((empty.height(), empty.width()), (3, 0));
+        assert_eq!(empty.neighbors4((1, 0)).count(), 0);
+        assert_eq!(empty.position(|_| true), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pos_in_empty_grid() {
+        Grid::<i32>::from(vec![vec![]; 3]).pos(0);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let rows = ["..#....#", ".##.##..", "....#.#.", "##.#...#", "...#.#.."];
+        let mut grid = Grid::from_strs(&rows);
+        grid[(4, 7)] = 'G';
+        assert_eq!(grid.height(), 5);
+        assert_eq!(grid.width(), 8);
+
+        // compare with the graph over the cells
+        let cost = |c: char| if c == '#' { 1 } else { 0 };
+        let mut edges = vec![];
+        for id in 0..grid.height() * grid.width() {
+            let p = grid.pos(id);
+            for q in grid.neighbors4(p) {
+                edges.push((id, grid.id(q), cost(grid[q])));
+            }
+        }
+        let graph = Graph::directed(grid.height() * grid.width(), edges.clone());
+        let unit = Graph::directed(
+            grid.height() * grid.width(),
+            edges
+                .iter()
+                .filter(|&&(_, _, w)| w == 0)
+                .map(|&(u, v, _)| (u, v, 1)),
+        );
+        for &start in &[(0, 0), (2, 5), (1, 1)] {
+            let dist = grid.bfs(start, |&c| c != '#');
+            let dist01 = grid.bfs01(start, |&c| Some(cost(c)));
+            let expected = bfs01(&unit, grid.id(start));
+            let expected01 = bfs01(&graph, grid.id(start));
+            for id in 0..grid.height() * grid.width() {
+                let (r, c) = grid.pos(id);
+                assert_eq!(dist[r][c], expected.dist(id));
+                assert_eq!(dist01[r][c], expected01.dist(id));
+            }
+        }
+        assert_eq!(grid.bfs((0, 0), |&c| c != '#')[4][7], None);
+        assert_eq!(grid.bfs01((0, 0), |&c| Some(cost(c)))[4][7], Some(1));
+    }
+}
@@ -0,0 +1,269 @@
+use super::Graph;
+use crate::math::num::Zero;
+use crate::structs::union_find::UnionFind;
+
+use core::cmp::Reverse;
+use core::ops::Add;
+use std::collections::BinaryHeap;
+
+/// A minimum spanning tree (or forest) with its total weight and edge ids.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpanningTree<W> {
+    pub weight: W,
+    /// The ids of the chosen edges in ascending order.
+    pub edges: Vec<usize>,
+}
+
+impl<W> SpanningTree<W>
+where
+    W: Copy + Zero + Add<Output = W>,
+{
+    fn new<V>(graph: &Graph<W>, edges: V) -> Self
+    where
+        V: IntoIterator<Item = usize>,
+    {
+        let mut edges = edges.into_iter().collect::<Vec<_>>();
+        edges.sort();
+        let weight = edges
+            .iter()
+            .fold(W::zero(), |acc, &id| acc + graph.edges()[id].weight);
+        Self { weight, edges }
+    }
+
+    /// Returns `self` if it spans all the vertices of `graph`.
+    fn filter_spanning(self, graph: &Graph<W>) -> Option<Self> {
+        if self.edges.len() + 1 >= graph.len() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns a minimum spanning forest of an undirected graph, which has the minimum spanning tree
+/// of each connected component.
+///
+/// This is Kruskal's algorithm in O(*E* log(*E*)).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{minimum_spanning_forest, Graph};
+///
+/// let graph = Graph::undirected(5, vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (3, 4, 5)]);
+/// let forest = minimum_spanning_forest(&graph);
+/// assert_eq!(forest.weight, 8);
+/// assert_eq!(forest.edges, vec![1, 2, 3]);
+/// ```
+pub fn minimum_spanning_forest<W>(graph: &Graph<W>) -> SpanningTree<W>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    let mut ids = (0..graph.edge_count()).collect::<Vec<_>>();
+    ids.sort_by_key(|&id| graph.edges()[id].weight);
+    let mut uf = UnionFind::new(graph.len());
+    let edges = ids.into_iter().filter(|&id| {
+        let e = &graph.edges()[id];
+        uf.union(e.from, e.to)
+    });
+    SpanningTree::new(graph, edges)
+}
+
+/// Returns a minimum spanning tree of an undirected graph, or `None` if it is not connected.
+///
+/// This is Kruskal's algorithm in O(*E* log(*E*)).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{kruskal, Graph};
+///
+/// let graph = Graph::undirected(4, vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (2, 3, 5)]);
+/// let tree = kruskal(&graph).unwrap();
+/// assert_eq!(tree.weight, 8);
+/// assert_eq!(tree.edges, vec![1, 2, 3]);
+/// assert_eq!(kruskal(&Graph::undirected(3, vec![(0, 1, 1)])), None);
+/// ```
+pub fn kruskal<W>(graph: &Graph<W>) -> Option<SpanningTree<W>>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    minimum_spanning_forest(graph).filter_spanning(graph)
+}
+
+/// Returns a minimum spanning tree of an undirected graph, or `None` if it is not connected.
+///
+/// This is Prim's algorithm with a binary heap in O(*E* log(*V*)).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{prim, Graph};
+///
+/// let graph = Graph::undirected(4, vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (2, 3, 5)]);
+/// let tree = prim(&graph).unwrap();
+/// assert_eq!(tree.weight, 8);
+/// assert_eq!(tree.edges, vec![1, 2, 3]);
+/// ```
+pub fn prim<W>(graph: &Graph<W>) -> Option<SpanningTree<W>>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    let n = graph.len();
+    let mut visited = vec![false; n];
+    let mut edges = vec![];
+    let mut heap = BinaryHeap::new();
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        heap.extend(
+            graph
+                .adj(root)
+                .iter()
+                .map(|e| Reverse((e.weight, e.id, e.to))),
+        );
+        while let Some(Reverse((_, id, v))) = heap.pop() {
+            if visited[v] {
+                continue;
+            }
+            visited[v] = true;
+            edges.push(id);
+            heap.extend(
+                graph
+                    .adj(v)
+                    .iter()
+                    .filter(|e| !visited[e.to])
+                    .map(|e| Reverse((e.weight, e.id, e.to))),
+            );
+        }
+    }
+    SpanningTree::new(graph, edges).filter_spanning(graph)
+}
+
+/// Returns a minimum spanning tree of an undirected graph, or `None` if it is not connected.
+///
+/// This is Borůvka's algorithm in O(*E* log(*V*)),
+/// which merges the components with their cheapest outgoing edges in each round.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{boruvka, Graph};
+///
+/// let graph = Graph::undirected(4, vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (2, 3, 5)]);
+/// let tree = boruvka(&graph).unwrap();
+/// assert_eq!(tree.weight, 8);
+/// assert_eq!(tree.edges, vec![1, 2, 3]);
+/// ```
+pub fn boruvka<W>(graph: &Graph<W>) -> Option<SpanningTree<W>>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    let n = graph.len();
+    let mut uf = UnionFind::new(n);
+    let mut edges = vec![];
+    loop {
+        // the cheapest edge from each component, where the ids break the ties
+        let mut cheapest = vec![None; n];
+        for e in graph.edges() {
+            let (a, b) = (uf.find(e.from), uf.find(e.to));
+            if a == b {
+                continue;
+            }
+            for &c in &[a, b] {
+                if cheapest[c].map_or(true, |best| (e.weight, e.id) < best) {
+                    cheapest[c] = Some((e.weight, e.id));
+                }
+            }
+        }
+        let mut merged = false;
+        for (_, id) in cheapest.into_iter().flatten() {
+            let e = &graph.edges()[id];
+            if uf.union(e.from, e.to) {
+                edges.push(id);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    SpanningTree::new(graph, edges).filter_spanning(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spanning_trees() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut rand = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+
+        for _ in 0..100 {
+            let n = rand(8) as usize + 1;
+            let m = rand(16) as usize;
+            let edges = (0..m)
+                .map(|_| {
+                    (
+                        rand(n as u64) as usize,
+                        rand(n as u64) as usize,
+                        rand(10) as i64,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let graph = Graph::undirected(n, edges.clone());
+
+            // the minimum over all the subsets of the edges forming a spanning forest
+            let forest = minimum_spanning_forest(&graph);
+            let components = {
+                let mut uf = UnionFind::new(n);
+                edges.iter().for_each(|&(u, v, _)| {
+                    uf.union(u, v);
+                });
+                uf.count()
+            };
+            let mut best = None;
+            for mask in 0_usize..1 << m {
+                if mask.count_ones() as usize + components != n {
+                    continue;
+                }
+                let mut uf = UnionFind::new(n);
+                let acyclic = (0..m)
+                    .filter(|&i| mask >> i & 1 == 1)
+                    .all(|i| uf.union(edges[i].0, edges[i].1));
+                if acyclic {
+                    let weight = (0..m)
+                        .filter(|&i| mask >> i & 1 == 1)
+                        .map(|i| edges[i].2)
+                        .sum::<i64>();
+                    best = Some(best.map_or(weight, |best: i64| best.min(weight)));
+                }
+            }
+            assert_eq!(Some(forest.weight), best);
+
+            let trees = vec![kruskal(&graph), prim(&graph), boruvka(&graph)];
+            for tree in trees {
+                assert_eq!(tree.is_some(), components == 1);
+                if let Some(tree) = tree {
+                    assert_eq!(tree.weight, forest.weight);
+                    assert_eq!(tree.edges.len(), n - 1);
+                    let mut uf = UnionFind::new(n);
+                    assert!(tree
+                        .edges
+                        .iter()
+                        .all(|&id| uf.union(edges[id].0, edges[id].1)));
+                    let weight = tree.edges.iter().map(|&id| edges[id].2).sum::<i64>();
+                    assert_eq!(weight, tree.weight);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use super::Graph;
+
+/// The strongly connected components of a directed graph.
+///
+/// The components are numbered in a topological order,
+/// i.e. every edge goes from a component to the same or a later one.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{Graph, SCC};
+///
+/// let graph = Graph::directed(5, vec![(1, 0), (0, 2), (2, 1), (3, 2), (3, 4)]);
+/// let scc = SCC::new(&graph);
+/// assert_eq!(scc.count(), 3);
+/// assert_eq!(scc.id(0), scc.id(1));
+/// assert!(scc.id(3) < scc.id(0));
+/// assert_eq!(scc.groups()[scc.id(2)], vec![0, 1, 2]);
+///
+/// let dag = scc.condensation(&graph);
+/// assert_eq!(dag.len(), 3);
+/// assert_eq!(dag.edge_count(), 2);
+/// ```
+///
+/// # Time complexity
+///
+/// This is Tarjan's algorithm in O(*V* + *E*) without recursion.
+#[derive(Clone, Debug)]
+pub struct StronglyConnectedComponents {
+    ids: Vec<usize>,
+    groups: Vec<Vec<usize>>,
+}
+
+/// Type alias for [`StronglyConnectedComponents`].
+pub type SCC = StronglyConnectedComponents;
+
+impl StronglyConnectedComponents {
+    pub fn new<W>(graph: &Graph<W>) -> Self {
+        const UNVISITED: usize = !0;
+        let n = graph.len();
+        let mut order = vec![UNVISITED; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = vec![];
+        // the vertices being visited and the next positions in their adjacencies
+        let mut calls = vec![];
+        let mut ids = vec![0; n];
+        let mut count = 0;
+        let mut time = 0;
+
+        for root in 0..n {
+            if order[root] != UNVISITED {
+                continue;
+            }
+            order[root] = time;
+            low[root] = time;
+            time += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            calls.push((root, 0));
+
+            while let Some((v, i)) = calls.last_mut() {
+                let v = *v;
+                if let Some(e) = graph.adj(v).get(*i) {
+                    *i += 1;
+                    let w = e.to;
+                    if order[w] == UNVISITED {
+                        order[w] = time;
+                        low[w] = time;
+                        time += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        calls.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(order[w]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(u, _)) = calls.last() {
+                    low[u] = low[u].min(low[v]);
+                }
+                if low[v] == order[v] {
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack[w] = false;
+                        ids[w] = count;
+                        if w == v {
+                            break;
+                        }
+                    }
+                    count += 1;
+                }
+            }
+        }
+
+        // Tarjan's algorithm finds the components in a reverse topological order
+        let mut groups = vec![vec![]; count];
+        for (v, id) in ids.iter_mut().enumerate() {
+            *id = count - 1 - *id;
+            groups[*id].push(v);
+        }
+        Self { ids, groups }
+    }
+
+    /// Returns the number of the components.
+    pub fn count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns the component of `v`.
+    pub fn id(&self, v: usize) -> usize {
+        self.ids[v]
+    }
+
+    pub fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    /// Returns the vertices in each component in ascending order.
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    /// Returns the DAG of the components, with one edge for each pair of the adjacent components.
+    pub fn condensation<W>(&self, graph: &Graph<W>) -> Graph {
+        let mut edges = graph
+            .edges()
+            .iter()
+            .map(|e| (self.ids[e.from], self.ids[e.to]))
+            .filter(|&(a, b)| a != b)
+            .collect::<Vec<_>>();
+        edges.sort();
+        edges.dedup();
+        Graph::directed(self.count(), edges)
+    }
+}
+
+/// A 2-SAT solver over `n` boolean variables.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::TwoSat;
+///
+/// // (x0 ∨ ¬x1) ∧ (x1 ∨ x2) ∧ (¬x0 ∨ ¬x2) ∧ (¬x2 ∨ ¬x2)
+/// let mut sat = TwoSat::new(3);
+/// sat.add_clause(0, true, 1, false);
+/// sat.add_clause(1, true, 2, true);
+/// sat.add_clause(0, false, 2, false);
+/// sat.add_clause(2, false, 2, false);
+/// assert_eq!(sat.solve(), Some(vec![true, true, false]));
+///
+/// sat.add_clause(0, false, 0, false);
+/// assert_eq!(sat.solve(), None);
+/// ```
+///
+/// # Time complexity
+///
+/// [`solve`](TwoSat::solve) is O(*n* + *m*) without recursion, where *m* is the number of the clauses.
+#[derive(Clone, Debug)]
+pub struct TwoSat {
+    n: usize,
+    // the implications between the literals, where `2i` is `x_i` and `2i + 1` is `¬x_i`
+    implications: Vec<(usize, usize)>,
+}
+
+impl TwoSat {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            implications: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn literal(i: usize, f: bool) -> usize {
+        2 * i + !f as usize
+    }
+
+    /// Adds a clause `(x_i == f) ∨ (x_j == g)`.
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        assert!(i < self.n && j < self.n);
+        self.implications
+            .push((Self::literal(i, !f), Self::literal(j, g)));
+        self.implications
+            .push((Self::literal(j, !g), Self::literal(i, f)));
+    }
+
+    /// Returns a satisfying assignment, or `None` if there is no such assignment.
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let graph = Graph::directed(2 * self.n, self.implications.iter().copied());
+        let scc = SCC::new(&graph);
+        (0..self.n)
+            .map(|i| {
+                let (t, f) = (scc.id(2 * i), scc.id(2 * i + 1));
+                // a literal is true if its negation can reach it
+                if t == f {
+                    None
+                } else {
+                    Some(t > f)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random(seed: &mut u64, m: u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed % m
+    }
+
+    #[test]
+    fn scc() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..100 {
+            let n = random(&mut seed, 10) as usize + 1;
+            let m = random(&mut seed, 20) as usize;
+            let edges = (0..m)
+                .map(|_| {
+                    let u = random(&mut seed, n as u64) as usize;
+                    (u, random(&mut seed, n as u64) as usize)
+                })
+                .collect::<Vec<_>>();
+            let graph = Graph::directed(n, edges.clone());
+            let scc = SCC::new(&graph);
+
+            let mut reach = vec![vec![false; n]; n];
+            for (v, row) in reach.iter_mut().enumerate() {
+                row[v] = true;
+            }
+            for &(u, v) in &edges {
+                reach[u][v] = true;
+            }
+            for k in 0..n {
+                for i in 0..n {
+                    for j in 0..n {
+                        reach[i][j] |= reach[i][k] && reach[k][j];
+                    }
+                }
+            }
+            for (u, v) in (0..n).flat_map(|u| (0..n).map(move |v| (u, v))) {
+                assert_eq!(scc.id(u) == scc.id(v), reach[u][v] && reach[v][u]);
+            }
+            for &(u, v) in &edges {
+                assert!(scc.id(u) <= scc.id(v));
+            }
+            for (id, group) in scc.groups().iter().enumerate() {
+                assert!(group.iter().all(|&v| scc.id(v) == id));
+            }
+            assert_eq!(scc.groups().iter().map(Vec::len).sum::<usize>(), n);
+
+            let dag = scc.condensation(&graph);
+            assert_eq!(dag.len(), scc.count());
+            for e in dag.edges() {
+                assert!(e.from < e.to);
+                let (a, b) = (scc.groups()[e.from][0], scc.groups()[e.to][0]);
+                assert!(reach[a][b]);
+            }
+        }
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 200_000;
+        let mut edges = (0..n - 1).map(|v| (v, v + 1)).collect::<Vec<_>>();
+        edges.push((n / 2, 0));
+        let scc = SCC::new(&Graph::directed(n, edges));
+        assert_eq!(scc.count(), n / 2);
+        assert_eq!(scc.groups()[0].len(), n / 2 + 1);
+        assert_eq!(scc.id(n - 1), n / 2 - 1);
+    }
+
+    #[test]
+    fn two_sat() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..200 {
+            let n = random(&mut seed, 6) as usize + 1;
+            let m = random(&mut seed, 12) as usize;
+            let mut sat = TwoSat::new(n);
+            let mut clauses = vec![];
+            for _ in 0..m {
+                let i = random(&mut seed, n as u64) as usize;
+                let j = random(&mut seed, n as u64) as usize;
+                let (f, g) = (random(&mut seed, 2) == 0, random(&mut seed, 2) == 0);
+                sat.add_clause(i, f, j, g);
+                clauses.push((i, f, j, g));
+            }
+            let satisfies = |x: &[bool]| clauses.iter().all(|&(i, f, j, g)| x[i] == f || x[j] == g);
+            let exists = (0_usize..1 << n)
+                .any(|mask| satisfies(&(0..n).map(|i| mask >> i & 1 == 1).collect::<Vec<_>>()));
+            match sat.solve() {
+                Some(x) => assert!(satisfies(&x)),
+                None => assert!(!exists),
+            }
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use super::Graph;
+use crate::math::num::Zero;
+
+use core::cmp::Reverse;
+use core::ops::Add;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// The shortest paths from a source, with the last edge of each path to reconstruct it.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<W> {
+    dist: Vec<Option<W>>,
+    // the previous vertex and the edge id on the shortest path
+    prev: Vec<Option<(usize, usize)>>,
+}
+
+impl<W: Clone> ShortestPaths<W> {
+    fn new(n: usize) -> Self {
+        Self {
+            dist: vec![None; n],
+            prev: vec![None; n],
+        }
+    }
+
+    /// Returns the distance to `v`, or `None` if `v` is unreachable.
+    pub fn dist(&self, v: usize) -> Option<W> {
+        self.dist[v].clone()
+    }
+
+    /// Returns the distances to all the vertices.
+    pub fn dists(&self) -> &[Option<W>] {
+        &self.dist
+    }
+
+    /// Returns the vertices on a shortest path from the source to `v`, including both ends.
+    pub fn path(&self, v: usize) -> Option<Vec<usize>> {
+        self.dist[v].as_ref()?;
+        let mut path = vec![v];
+        let mut v = v;
+        while let Some((u, _)) = self.prev[v] {
+            path.push(u);
+            v = u;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the edge ids on a shortest path from the source to `v`.
+    pub fn path_edges(&self, v: usize) -> Option<Vec<usize>> {
+        self.dist[v].as_ref()?;
+        let mut path = vec![];
+        let mut v = v;
+        while let Some((u, id)) = self.prev[v] {
+            path.push(id);
+            v = u;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Returns the shortest paths from `source` counting the number of edges.
+///
+/// This is O(*V* + *E*).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{bfs, Graph};
+///
+/// let graph = Graph::directed(5, vec![(0, 1), (1, 2), (0, 3), (3, 2)]);
+/// let paths = bfs(&graph, 0);
+/// assert_eq!(paths.dist(2), Some(2));
+/// assert_eq!(paths.dist(4), None);
+/// assert_eq!(paths.path(2), Some(vec![0, 1, 2]));
+/// ```
+pub fn bfs<W>(graph: &Graph<W>, source: usize) -> ShortestPaths<usize> {
+    let mut ret = ShortestPaths::new(graph.len());
+    let mut queue = VecDeque::new();
+    ret.dist[source] = Some(0);
+    queue.push_back((source, 0));
+    while let Some((u, d)) = queue.pop_front() {
+        for e in graph.adj(u) {
+            if ret.dist[e.to].is_none() {
+                ret.dist[e.to] = Some(d + 1);
+                ret.prev[e.to] = Some((u, e.id));
+                queue.push_back((e.to, d + 1));
+            }
+        }
+    }
+    ret
+}
+
+/// Returns the shortest paths from `source`, where the edges of weight zero cost 0
+/// and the other edges cost 1.
+///
+/// This is O(*V* + *E*).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{bfs01, Graph};
+///
+/// let graph = Graph::directed(4, vec![(0, 1, 1), (1, 2, 1), (0, 3, 0), (3, 2, 1)]);
+/// let paths = bfs01(&graph, 0);
+/// assert_eq!(paths.dist(2), Some(1));
+/// assert_eq!(paths.path(2), Some(vec![0, 3, 2]));
+/// ```
+pub fn bfs01<W: Zero + PartialEq>(graph: &Graph<W>, source: usize) -> ShortestPaths<usize> {
+    let mut ret = ShortestPaths::new(graph.len());
+    let mut deque = VecDeque::new();
+    ret.dist[source] = Some(0);
+    deque.push_back((source, 0));
+    while let Some((u, d)) = deque.pop_front() {
+        if ret.dist[u] != Some(d) {
+            continue;
+        }
+        for e in graph.adj(u) {
+            let zero = e.weight == W::zero();
+            let next = if zero { d } else { d + 1 };
+            if ret.dist[e.to].map_or(true, |dist| next < dist) {
+                ret.dist[e.to] = Some(next);
+                ret.prev[e.to] = Some((u, e.id));
+                if zero {
+                    deque.push_front((e.to, next));
+                } else {
+                    deque.push_back((e.to, next));
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// Returns the shortest paths from `source`, where all the weights are non-negative.
+///
+/// This is O((*V* + *E*) log(*V*)).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{dijkstra, Graph};
+///
+/// let graph = Graph::undirected(4, vec![(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 5)]);
+/// let paths = dijkstra(&graph, 0);
+/// assert_eq!(paths.dists(), &[Some(0), Some(3), Some(1), Some(8)]);
+/// assert_eq!(paths.path(3), Some(vec![0, 2, 1, 3]));
+/// assert_eq!(paths.path_edges(3), Some(vec![1, 2, 3]));
+/// ```
+pub fn dijkstra<W>(graph: &Graph<W>, source: usize) -> ShortestPaths<W>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    let mut ret = ShortestPaths::new(graph.len());
+    let mut heap = BinaryHeap::new();
+    ret.dist[source] = Some(W::zero());
+    heap.push(Reverse((W::zero(), source)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if ret.dist[u] != Some(d) {
+            continue;
+        }
+        for e in graph.adj(u) {
+            let next = d + e.weight;
+            if ret.dist[e.to].map_or(true, |dist| next < dist) {
+                ret.dist[e.to] = Some(next);
+                ret.prev[e.to] = Some((u, e.id));
+                heap.push(Reverse((next, e.to)));
+            }
+        }
+    }
+    ret
+}
+
+/// Returns the shortest paths from `source` allowing negative weights,
+/// or the vertices of a negative cycle reachable from `source` in order.
+///
+/// An undirected edge is treated as two directed edges.
+///
+/// This is O(*V* *E*).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{bellman_ford, Graph};
+///
+/// let graph = Graph::directed(3, vec![(0, 1, 4), (1, 2, -3), (0, 2, 2)]);
+/// let paths = bellman_ford(&graph, 0).unwrap();
+/// assert_eq!(paths.dist(2), Some(1));
+///
+/// let graph = Graph::directed(4, vec![(0, 1, 1), (1, 2, -2), (2, 1, 1), (3, 0, -5)]);
+/// assert_eq!(bellman_ford(&graph, 0).unwrap_err(), vec![1, 2]);
+/// ```
+pub fn bellman_ford<W>(graph: &Graph<W>, source: usize) -> Result<ShortestPaths<W>, Vec<usize>>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    let n = graph.len();
+    let mut ret = ShortestPaths::new(n);
+    ret.dist[source] = Some(W::zero());
+    // a shortest path has at most `n - 1` edges, so an update in the `n`-th round means a cycle
+    let mut updated = None;
+    for _ in 0..n {
+        updated = None;
+        for u in 0..n {
+            let d = match ret.dist[u] {
+                Some(d) => d,
+                None => continue,
+            };
+            for e in graph.adj(u) {
+                let next = d + e.weight;
+                if ret.dist[e.to].map_or(true, |dist| next < dist) {
+                    ret.dist[e.to] = Some(next);
+                    ret.prev[e.to] = Some((u, e.id));
+                    updated = Some(e.to);
+                }
+            }
+        }
+        if updated.is_none() {
+            return Ok(ret);
+        }
+    }
+
+    // going back `n` times from the last updated vertex reaches the cycle
+    let mut v = updated.unwrap();
+    for _ in 0..n {
+        v = ret.prev[v].unwrap().0;
+    }
+    let mut cycle = vec![v];
+    let mut u = ret.prev[v].unwrap().0;
+    while u != v {
+        cycle.push(u);
+        u = ret.prev[u].unwrap().0;
+    }
+    cycle.reverse();
+    let min = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(min);
+    Err(cycle)
+}
+
+/// The shortest paths between all the pairs of the vertices.
+#[derive(Clone, Debug)]
+pub struct AllPairsShortestPaths<W> {
+    dist: Vec<Vec<Option<W>>>,
+    // the next vertex on the shortest path
+    next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W> AllPairsShortestPaths<W>
+where
+    W: Copy + Ord + Zero,
+{
+    /// Returns the distance from `u` to `v`, or `None` if `v` is unreachable from `u`.
+    ///
+    /// The distance is meaningless if the graph has a negative cycle.
+    pub fn dist(&self, u: usize, v: usize) -> Option<W> {
+        self.dist[u][v]
+    }
+
+    /// Returns whether the graph has a negative cycle.
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.dist.len()).any(|v| self.dist[v][v].map_or(false, |d| d < W::zero()))
+    }
+
+    /// Returns the vertices on a shortest path from `u` to `v`, including both ends.
+    ///
+    /// The path is meaningless if the graph has a negative cycle.
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        self.dist[u][v]?;
+        let mut path = vec![u];
+        let mut u = u;
+        while u != v {
+            u = self.next[u][v]?;
+            path.push(u);
+            if path.len() > self.dist.len() {
+                return None;
+            }
+        }
+        Some(path)
+    }
+}
+
+/// Returns the shortest paths between all the pairs of the vertices.
+///
+/// If the graph has a negative cycle, this stops as soon as it is found,
+/// so only [`has_negative_cycle`](AllPairsShortestPaths::has_negative_cycle) is meaningful.
+///
+/// This is O(*V*^3).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{floyd_warshall, Graph};
+///
+/// let graph = Graph::directed(4, vec![(0, 1, 3), (1, 2, -1), (0, 2, 5), (2, 3, 2)]);
+/// let paths = floyd_warshall(&graph);
+/// assert_eq!(paths.dist(0, 3), Some(4));
+/// assert_eq!(paths.dist(3, 0), None);
+/// assert_eq!(paths.path(0, 3), Some(vec![0, 1, 2, 3]));
+/// assert!(!paths.has_negative_cycle());
+/// ```
+pub fn floyd_warshall<W>(graph: &Graph<W>) -> AllPairsShortestPaths<W>
+where
+    W: Copy + Ord + Zero + Add<Output = W>,
+{
+    let n = graph.len();
+    let mut dist = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for v in 0..n {
+        dist[v][v] = Some(W::zero());
+        next[v][v] = Some(v);
+        for e in graph.adj(v) {
+            if dist[v][e.to].map_or(true, |d| e.weight < d) {
+                dist[v][e.to] = Some(e.weight);
+                next[v][e.to] = Some(e.to);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let ik = match dist[i][k] {
+                Some(d) => d,
+                None => continue,
+            };
+            for j in 0..n {
+                if let Some(kj) = dist[k][j] {
+                    if dist[i][j].map_or(true, |d| ik + kj < d) {
+                        dist[i][j] = Some(ik + kj);
+                        next[i][j] = next[i][k];
+                    }
+                }
+            }
+        }
+        // going on would repeat the cycle and the distances could overflow
+        if (0..n).any(|v| dist[v][v].map_or(false, |d| d < W::zero())) {
+            break;
+        }
+    }
+    AllPairsShortestPaths { dist, next }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_edges(n: usize, m: usize, max: u64, seed: u64) -> Vec<(usize, usize, i64)> {
+        let mut seed = seed;
+        let mut rand = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        (0..m)
+            .map(|_| {
+                let u = rand(n as u64) as usize;
+                let v = rand(n as u64) as usize;
+                (u, v, rand(max) as i64)
+            })
+            .collect()
+    }
+
+    /// Returns the length of `path`, checking that it is a path in `graph`.
+    fn path_len(graph: &Graph<i64>, path: &[usize]) -> i64 {
+        path.windows(2)
+            .map(|w| {
+                graph
+                    .adj(w[0])
+                    .iter()
+                    .filter(|e| e.to == w[1])
+                    .map(|e| e.weight)
+                    .min()
+                    .unwrap()
+            })
+            .sum()
+    }
+
+    /// Checks that the path to `v` is consistent with its edges and has the length `len`.
+    fn check_path(graph: &Graph<i64>, paths: &ShortestPaths<i64>, source: usize, v: usize) {
+        let vertices = paths.path(v).unwrap();
+        let edges = paths.path_edges(v).unwrap();
+        assert_eq!(vertices.len(), edges.len() + 1);
+        assert_eq!(vertices[0], source);
+        assert_eq!(vertices[vertices.len() - 1], v);
+        let mut sum = 0;
+        for (i, &id) in edges.iter().enumerate() {
+            let e = graph.edges()[id];
+            let ends = (vertices[i], vertices[i + 1]);
+            assert!(ends == (e.from, e.to) || !graph.is_directed() && ends == (e.to, e.from));
+            sum += e.weight;
+        }
+        assert_eq!(Some(sum), paths.dist(v));
+    }
+
+    #[test]
+    fn shortest_paths() {
+        for seed in 1..=20 {
+            let n = 12;
+            let edges = random_edges(n, 30, 4, 0x2545_f491_4f6c_dd1d ^ seed);
+            for &directed in &[true, false] {
+                let build = |f: &dyn Fn(i64) -> i64| {
+                    let edges = edges.iter().map(|&(u, v, w)| (u, v, f(w)));
+                    if directed {
+                        Graph::directed(n, edges)
+                    } else {
+                        Graph::undirected(n, edges)
+                    }
+                };
+                let graph = build(&|w| w);
+                let unit = build(&|_| 1);
+                let binary = build(&|w| w % 2);
+                let all = floyd_warshall(&graph);
+                let unit_all = floyd_warshall(&unit);
+                let binary_all = floyd_warshall(&binary);
+                assert!(!all.has_negative_cycle());
+                for source in 0..n {
+                    let dijkstra = dijkstra(&graph, source);
+                    let bellman_ford = bellman_ford(&graph, source).unwrap();
+                    let bfs = bfs(&unit, source);
+                    let bfs01 = bfs01(&binary, source);
+                    for v in 0..n {
+                        let dist = all.dist(source, v);
+                        assert_eq!(dijkstra.dist(v), dist);
+                        assert_eq!(bellman_ford.dist(v), dist);
+                        assert_eq!(bfs.dist(v).map(|d| d as i64), unit_all.dist(source, v));
+                        assert_eq!(bfs01.dist(v).map(|d| d as i64), binary_all.dist(source, v));
+                        if let Some(dist) = dist {
+                            check_path(&graph, &dijkstra, source, v);
+                            check_path(&graph, &bellman_ford, source, v);
+                            let path = all.path(source, v).unwrap();
+                            assert_eq!((path[0], path[path.len() - 1]), (source, v));
+                            assert_eq!(path_len(&graph, &path), dist);
+                        } else {
+                            assert_eq!(dijkstra.path(v), None);
+                            assert_eq!(all.path(source, v), None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let edges = vec![(0, 1, 2), (1, 2, 3), (2, 3, -4), (3, 1, 0), (4, 0, 1)];
+        let graph = Graph::directed(5, edges);
+        assert_eq!(bellman_ford(&graph, 0).unwrap_err(), vec![1, 2, 3]);
+        assert_eq!(bellman_ford(&graph, 4).unwrap_err(), vec![1, 2, 3]);
+        assert!(floyd_warshall(&graph).has_negative_cycle());
+
+        let graph = Graph::directed(5, vec![(0, 1, -2), (1, 2, -3), (3, 4, -1), (4, 3, 0)]);
+        let paths = bellman_ford(&graph, 0).unwrap();
+        assert_eq!(paths.dists(), &[Some(0), Some(-2), Some(-5), None, None]);
+        assert_eq!(bellman_ford(&graph, 3).unwrap_err(), vec![3, 4]);
+    }
+
+    #[test]
+    fn dense_negative_cycle() {
+        // repeating the negative cycles would overflow `i64` without stopping early
+        let n = 70;
+        let edges = (0..n)
+            .flat_map(|u| {
+                (0..n)
+                    .filter(move |&v| v != u)
+                    .map(move |v| (u, v, -1_000_000_000))
+            })
+            .collect::<Vec<_>>();
+        let paths = floyd_warshall(&Graph::directed(n, edges));
+        assert!(paths.has_negative_cycle());
+
+        let mut edges = (0..n).map(|v| (v, (v + 1) % n, 1)).collect::<Vec<_>>();
+        edges.push((n - 1, 0, -(n as i64)));
+        let paths = floyd_warshall(&Graph::directed(n, edges));
+        assert!(paths.has_negative_cycle());
+    }
+}
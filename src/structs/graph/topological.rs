@@ -0,0 +1,300 @@
+use super::{Edge, Graph};
+use crate::math::num::Semigroup;
+
+use core::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// The vertices waiting to be ordered in Kahn's algorithm.
+trait Frontier {
+    fn push(&mut self, v: usize);
+    fn pop(&mut self) -> Option<usize>;
+}
+
+impl Frontier for VecDeque<usize> {
+    fn push(&mut self, v: usize) {
+        self.push_back(v);
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        self.pop_front()
+    }
+}
+
+impl Frontier for BinaryHeap<Reverse<usize>> {
+    fn push(&mut self, v: usize) {
+        BinaryHeap::push(self, Reverse(v));
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        BinaryHeap::pop(self).map(|Reverse(v)| v)
+    }
+}
+
+fn kahn<W, Q: Frontier>(graph: &Graph<W>, mut frontier: Q) -> Result<Vec<usize>, Vec<usize>> {
+    let n = graph.len();
+    let mut indeg = vec![0; n];
+    for u in 0..n {
+        for e in graph.adj(u) {
+            indeg[e.to] += 1;
+        }
+    }
+    (0..n)
+        .filter(|&v| indeg[v] == 0)
+        .for_each(|v| frontier.push(v));
+    let mut order = Vec::with_capacity(n);
+    while let Some(u) = frontier.pop() {
+        order.push(u);
+        for e in graph.adj(u) {
+            indeg[e.to] -= 1;
+            if indeg[e.to] == 0 {
+                frontier.push(e.to);
+            }
+        }
+    }
+    if order.len() == n {
+        return Ok(order);
+    }
+
+    // every remaining vertex has an edge from another remaining one,
+    // so going back `n` times along such edges reaches a cycle
+    let mut prev = vec![None; n];
+    for u in (0..n).filter(|&u| indeg[u] > 0) {
+        for e in graph.adj(u) {
+            prev[e.to] = Some(u);
+        }
+    }
+    let mut v = (0..n).find(|&v| indeg[v] > 0).unwrap();
+    for _ in 0..n {
+        v = prev[v].unwrap();
+    }
+    let mut cycle = vec![v];
+    let mut u = prev[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = prev[u].unwrap();
+    }
+    cycle.reverse();
+    let min = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(min);
+    Err(cycle)
+}
+
+/// Returns a topological order of the vertices,
+/// or the vertices of a cycle in order if the graph is not a DAG.
+///
+/// An undirected edge is treated as two directed edges.
+///
+/// This is Kahn's algorithm in O(*V* + *E*).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{topological_sort, Graph};
+///
+/// let graph = Graph::directed(4, vec![(2, 0), (0, 1), (2, 3), (3, 1)]);
+/// let order = topological_sort(&graph).unwrap();
+/// assert_eq!(order.len(), 4);
+/// assert_eq!(order[0], 2);
+/// assert_eq!(order[3], 1);
+///
+/// let graph = Graph::directed(4, vec![(0, 1), (3, 2), (1, 3), (2, 1)]);
+/// assert_eq!(topological_sort(&graph).unwrap_err(), vec![1, 3, 2]);
+/// ```
+pub fn topological_sort<W>(graph: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    kahn(graph, VecDeque::new())
+}
+
+/// Returns the lexicographically smallest topological order of the vertices,
+/// or the vertices of a cycle in order if the graph is not a DAG.
+///
+/// An undirected edge is treated as two directed edges.
+///
+/// This is Kahn's algorithm with a binary heap in O(*V* log(*V*) + *E*).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::graph::{lexicographic_topological_sort, Graph};
+///
+/// let graph = Graph::directed(5, vec![(3, 0), (4, 1), (1, 0), (2, 1)]);
+/// assert_eq!(
+///     lexicographic_topological_sort(&graph),
+///     Ok(vec![2, 3, 4, 1, 0])
+/// );
+/// ```
+pub fn lexicographic_topological_sort<W>(graph: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    kahn(graph, BinaryHeap::new())
+}
+
+/// Folds a DP over a DAG in a topological order,
+/// or returns the vertices of a cycle in order if the graph is not a DAG.
+///
+/// The value of each vertex is `init` combined by `M` with `transfer(value of from, edge)`
+/// for all the edges into it, where `None` means no value.
+///
+/// This is O(*V* + *E*) operations.
+///
+/// # Panics
+///
+/// Panics if `init.len()` is not the number of the vertices.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::math::modint::types::ModInt998244353 as Mint;
+/// use lib_rust::math::num::alge_struct::types::{AddMonoid, MaxMonoid};
+/// use lib_rust::structs::graph::{dag_dp, Graph};
+///
+/// let graph = Graph::directed(4, vec![(0, 1, 2), (0, 2, 1), (1, 3, 3), (2, 3, 5), (2, 1, 1)]);
+/// let mut init = vec![None; 4];
+/// init[0] = Some(0);
+///
+/// // the longest paths from 0
+/// let longest = dag_dp::<MaxMonoid<i64>, _, _>(&graph, init, |&d, e| d + e.weight).unwrap();
+/// assert_eq!(longest, vec![Some(0), Some(2), Some(1), Some(6)]);
+///
+/// // the numbers of the paths from 0
+/// let mut init = vec![None; 4];
+/// init[0] = Some(Mint::one());
+/// let count = dag_dp::<AddMonoid<Mint>, _, _>(&graph, init, |&c, _| c).unwrap();
+/// assert_eq!(count[3].unwrap(), 3);
+/// ```
+pub fn dag_dp<M, W, F>(
+    graph: &Graph<W>,
+    init: Vec<Option<M::Set>>,
+    mut transfer: F,
+) -> Result<Vec<Option<M::Set>>, Vec<usize>>
+where
+    M: Semigroup,
+    F: FnMut(&M::Set, &Edge<W>) -> M::Set,
+{
+    assert_eq!(init.len(), graph.len());
+    let order = topological_sort(graph)?;
+    let mut dp = init;
+    for u in order {
+        let value = match &dp[u] {
+            Some(value) => value.clone(),
+            None => continue,
+        };
+        for e in graph.adj(u) {
+            let next = transfer(&value, e);
+            dp[e.to] = Some(match &dp[e.to] {
+                Some(cur) => M::operate(cur, &next),
+                None => next,
+            });
+        }
+    }
+    Ok(dp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::num::alge_struct::types::MaxMonoid;
+    use crate::structs::graph::{bellman_ford, SCC};
+
+    fn random(seed: &mut u64, m: u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed % m
+    }
+
+    fn is_topological(n: usize, edges: &[(usize, usize, i64)], order: &[usize]) -> bool {
+        let mut pos = vec![n; n];
+        for (i, &v) in order.iter().enumerate() {
+            pos[v] = i;
+        }
+        order.len() == n && pos.iter().all(|&i| i < n) && edges.iter().all(|e| pos[e.0] < pos[e.1])
+    }
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        let mut ret = vec![];
+        for p in permutations(n - 1) {
+            for i in 0..n {
+                let mut p = p.clone();
+                p.insert(i, n - 1);
+                ret.push(p);
+            }
+        }
+        ret.sort();
+        ret
+    }
+
+    #[test]
+    fn topological_orders() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..200 {
+            let n = random(&mut seed, 6) as usize + 1;
+            let m = random(&mut seed, 10) as usize;
+            let edges = (0..m)
+                .map(|_| {
+                    let u = random(&mut seed, n as u64) as usize;
+                    (u, random(&mut seed, n as u64) as usize, 0)
+                })
+                .collect::<Vec<_>>();
+            let graph = Graph::directed(n, edges.clone());
+            let acyclic = SCC::new(&graph).count() == n && edges.iter().all(|e| e.0 != e.1);
+
+            match topological_sort(&graph) {
+                Ok(order) => assert!(acyclic && is_topological(n, &edges, &order)),
+                Err(cycle) => {
+                    assert!(!acyclic);
+                    assert!(cycle[0] == *cycle.iter().min().unwrap());
+                    for i in 0..cycle.len() {
+                        let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                        assert!(edges.iter().any(|e| (e.0, e.1) == (u, v)));
+                    }
+                }
+            }
+            let expected = permutations(n)
+                .into_iter()
+                .find(|p| is_topological(n, &edges, p));
+            match lexicographic_topological_sort(&graph) {
+                Ok(order) => assert_eq!(Some(order), expected),
+                Err(_) => assert_eq!(expected, None),
+            }
+        }
+    }
+
+    #[test]
+    fn longest_paths() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..100 {
+            let n = random(&mut seed, 10) as usize + 1;
+            let m = random(&mut seed, 20) as usize;
+            // edges from a smaller label to a larger one under a random relabeling
+            let mut label = (0..n).collect::<Vec<_>>();
+            for i in 1..n {
+                label.swap(i, random(&mut seed, i as u64 + 1) as usize);
+            }
+            let edges = (0..m)
+                .filter_map(|_| {
+                    let u = random(&mut seed, n as u64) as usize;
+                    let v = random(&mut seed, n as u64) as usize;
+                    let w = random(&mut seed, 10) as i64;
+                    if u < v {
+                        Some((label[u], label[v], w))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            let graph = Graph::directed(n, edges.clone());
+            let negated = Graph::directed(n, edges.iter().map(|&(u, v, w)| (u, v, -w)));
+
+            let source = random(&mut seed, n as u64) as usize;
+            let mut init = vec![None; n];
+            init[source] = Some(0);
+            let longest =
+                dag_dp::<MaxMonoid<i64>, _, _>(&graph, init, |&d, e| d + e.weight).unwrap();
+            let shortest = bellman_ford(&negated, source).unwrap();
+            for (v, &d) in longest.iter().enumerate() {
+                assert_eq!(d, shortest.dist(v).map(|d| -d));
+            }
+        }
+    }
+}
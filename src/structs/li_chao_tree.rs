@@ -0,0 +1,471 @@
+use crate::math::num::alge_struct::types::MinMax;
+
+use core::marker::PhantomData;
+use core::mem::swap;
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+/// A line `y = a * x + b`.
+type Line = (i64, i64);
+
+fn eval(&(a, b): &Line, x: i64) -> i64 {
+    a * x + b
+}
+
+/// Returns whether `lhs` is strictly better than `rhs` in `M`.
+fn wins<M: MinMax<Set = i64>>(lhs: i64, rhs: i64) -> bool {
+    lhs != rhs && (lhs < rhs) == M::IS_MIN
+}
+
+/// A Li Chao tree over fixed x-coordinates, answering the minimum or maximum
+/// of the lines at a point in O(log(*n*)).
+///
+/// `M` is [`MinMonoid<i64>`] to take the minimum or [`MaxMonoid<i64>`] to take the maximum.
+/// `a * x + b` must fit in `i64` for all the lines and x-coordinates.
+///
+/// [`MinMonoid<i64>`]: crate::math::num::alge_struct::types::MinMonoid
+/// [`MaxMonoid<i64>`]: crate::math::num::alge_struct::types::MaxMonoid
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::li_chao_tree::LiChaoTree;
+/// use lib_rust::math::num::alge_struct::types::MinMonoid;
+///
+/// let mut tree = LiChaoTree::<MinMonoid<i64>>::new(vec![-2, 0, 1, 3, 5]);
+/// assert_eq!(tree.query(0), i64::max_value());
+/// tree.add_line(1, 0);
+/// tree.add_line(-1, 2);
+/// assert_eq!(tree.query(-2), -2);
+/// assert_eq!(tree.query(3), -1);
+/// tree.add_segment(0, -5, 0..=1);
+/// assert_eq!(tree.query(1), -5);
+/// assert_eq!(tree.query(3), -1);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm       | Worst case        |
+/// | --------------- | ----------------- |
+/// | [`add_line`]    | O(log(*n*))       |
+/// | [`add_segment`] | O(log(*n*)^2)     |
+/// | [`query`]       | O(log(*n*))       |
+///
+/// [`add_line`]: LiChaoTree::add_line
+/// [`add_segment`]: LiChaoTree::add_segment
+/// [`query`]: LiChaoTree::query
+pub struct LiChaoTree<M> {
+    xs: Vec<i64>,
+    // the node `k` covers `xs[l..=r]`, and its children are `2k + 1` and `2k + 2`
+    lines: Vec<Option<Line>>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+impl<M: MinMax<Set = i64>> LiChaoTree<M> {
+    /// Creates an empty tree answering the queries at `xs`.
+    pub fn new(xs: Vec<i64>) -> Self {
+        let mut xs = xs;
+        xs.sort();
+        xs.dedup();
+        let lines = vec![None; 4 * xs.len().max(1)];
+        Self {
+            xs,
+            lines,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn add(&mut self, k: usize, l: usize, r: usize, line: Line) {
+        let mut line = line;
+        let (mut k, mut l, mut r) = (k, l, r);
+        loop {
+            let cur = match &mut self.lines[k] {
+                Some(cur) => cur,
+                None => {
+                    self.lines[k] = Some(line);
+                    return;
+                }
+            };
+            let m = (l + r) / 2;
+            if wins::<M>(eval(&line, self.xs[m]), eval(cur, self.xs[m])) {
+                swap(cur, &mut line);
+            }
+            if l == r {
+                return;
+            }
+            // `line` loses at `m`, so it wins on at most one side
+            if wins::<M>(eval(&line, self.xs[l]), eval(cur, self.xs[l])) {
+                k = 2 * k + 1;
+                r = m;
+            } else if wins::<M>(eval(&line, self.xs[r]), eval(cur, self.xs[r])) {
+                k = 2 * k + 2;
+                l = m + 1;
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// Adds a line `y = a * x + b`.
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        if !self.xs.is_empty() {
+            self.add(0, 0, self.xs.len() - 1, (a, b));
+        }
+    }
+
+    fn add_range(&mut self, k: usize, l: usize, r: usize, range: (usize, usize), line: Line) {
+        if range.1 <= l || r < range.0 {
+            return;
+        }
+        if range.0 <= l && r < range.1 {
+            self.add(k, l, r, line);
+            return;
+        }
+        let m = (l + r) / 2;
+        self.add_range(2 * k + 1, l, m, range, line);
+        self.add_range(2 * k + 2, m + 1, r, range, line);
+    }
+
+    /// Adds a segment `y = a * x + b` for `x` in `range`.
+    pub fn add_segment<R: RangeBounds<i64>>(&mut self, a: i64, b: i64, range: R) {
+        // the first index of `x` not less than `bound`, or greater if `strict`
+        let index = |bound: i64, strict: bool| {
+            self.xs
+                .binary_search_by(|&y| {
+                    if y < bound || strict && y == bound {
+                        core::cmp::Ordering::Less
+                    } else {
+                        core::cmp::Ordering::Greater
+                    }
+                })
+                .unwrap_err()
+        };
+        let l = match range.start_bound() {
+            Included(&l) => index(l, false),
+            Excluded(&l) => index(l, true),
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => index(r, true),
+            Excluded(&r) => index(r, false),
+            Unbounded => self.xs.len(),
+        };
+        if l < r {
+            self.add_range(0, 0, self.xs.len() - 1, (l, r), (a, b));
+        }
+    }
+
+    /// Returns the minimum or maximum of the lines at `x`, or `M::id()` if there is no line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not one of the x-coordinates given at the construction.
+    pub fn query(&self, x: i64) -> i64 {
+        let i = self
+            .xs
+            .binary_search(&x)
+            .expect("`x` must be one of the x-coordinates");
+        let (mut k, mut l, mut r) = (0, 0, self.xs.len() - 1);
+        let mut ret = M::id();
+        loop {
+            if let Some(line) = &self.lines[k] {
+                ret = M::operate(&ret, &eval(line, x));
+            }
+            if l == r {
+                return ret;
+            }
+            let m = (l + r) / 2;
+            if i <= m {
+                k = 2 * k + 1;
+                r = m;
+            } else {
+                k = 2 * k + 2;
+                l = m + 1;
+            }
+        }
+    }
+}
+
+struct Node {
+    line: Line,
+    children: [Option<usize>; 2],
+}
+
+/// A Li Chao tree over all the integers in a range, allocating the nodes on demand.
+///
+/// This works like [`LiChaoTree`] with x-coordinates up to about 1e18,
+/// using O(log(*w*)) nodes for each line, where *w* is the width of the range.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::li_chao_tree::DynamicLiChaoTree;
+/// use lib_rust::math::num::alge_struct::types::MaxMonoid;
+///
+/// let mut tree = DynamicLiChaoTree::<MaxMonoid<i64>>::new(-1_000_000_000_000_000_000..1_000_000_000_000_000_000);
+/// tree.add_line(2, 0);
+/// tree.add_line(-1, 3);
+/// assert_eq!(tree.query(-5), 8);
+/// assert_eq!(tree.query(400_000_000_000_000_000), 800_000_000_000_000_000);
+/// tree.add_segment(0, 100, ..0);
+/// assert_eq!(tree.query(-5), 100);
+/// assert_eq!(tree.query(5), 10);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm       | Worst case        |
+/// | --------------- | ----------------- |
+/// | [`add_line`]    | O(log(*w*))       |
+/// | [`add_segment`] | O(log(*w*)^2)     |
+/// | [`query`]       | O(log(*w*))       |
+///
+/// [`add_line`]: DynamicLiChaoTree::add_line
+/// [`add_segment`]: DynamicLiChaoTree::add_segment
+/// [`query`]: DynamicLiChaoTree::query
+pub struct DynamicLiChaoTree<M> {
+    // the inclusive range of x
+    lo: i64,
+    hi: i64,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    _phantom: PhantomData<fn() -> M>,
+}
+
+/// Returns `floor((l + r) / 2)` without overflow.
+fn mid(l: i64, r: i64) -> i64 {
+    ((l as i128 + r as i128) >> 1) as i64
+}
+
+impl<M: MinMax<Set = i64>> DynamicLiChaoTree<M> {
+    /// Creates an empty tree answering the queries in `range`.
+    pub fn new<R: RangeBounds<i64>>(range: R) -> Self {
+        let lo = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => i64::min_value(),
+        };
+        let hi = match range.end_bound() {
+            Included(&r) => r,
+            Excluded(&r) => r - 1,
+            Unbounded => i64::max_value(),
+        };
+        assert!(lo <= hi, "the range must not be empty");
+        Self {
+            lo,
+            hi,
+            nodes: vec![],
+            root: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn new_node(&mut self, line: Line) -> usize {
+        self.nodes.push(Node {
+            line,
+            children: [None, None],
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Adds `line` to the subtree of `node` covering `l..=r`, and returns the subtree.
+    fn add(&mut self, node: Option<usize>, l: i64, r: i64, line: Line) -> usize {
+        let root = match node {
+            Some(node) => node,
+            None => return self.new_node(line),
+        };
+        let (mut k, mut l, mut r) = (root, l, r);
+        let mut line = line;
+        loop {
+            let m = mid(l, r);
+            let cur = &mut self.nodes[k].line;
+            if wins::<M>(eval(&line, m), eval(cur, m)) {
+                swap(cur, &mut line);
+            }
+            if l == r {
+                return root;
+            }
+            let dir = if wins::<M>(eval(&line, l), eval(cur, l)) {
+                r = m;
+                0
+            } else if wins::<M>(eval(&line, r), eval(cur, r)) {
+                l = m + 1;
+                1
+            } else {
+                return root;
+            };
+            match self.nodes[k].children[dir] {
+                Some(child) => k = child,
+                None => {
+                    let child = self.new_node(line);
+                    self.nodes[k].children[dir] = Some(child);
+                    return root;
+                }
+            }
+        }
+    }
+
+    /// Adds a line `y = a * x + b`.
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        self.root = Some(self.add(self.root, self.lo, self.hi, (a, b)));
+    }
+
+    fn add_range(
+        &mut self,
+        node: Option<usize>,
+        l: i64,
+        r: i64,
+        range: (i64, i64),
+        line: Line,
+    ) -> Option<usize> {
+        if range.1 < l || r < range.0 {
+            return node;
+        }
+        if range.0 <= l && r <= range.1 {
+            return Some(self.add(node, l, r, line));
+        }
+        let node = match node {
+            Some(node) => node,
+            // a line which never wins
+            None => self.new_node((0, M::id())),
+        };
+        let m = mid(l, r);
+        let left = self.add_range(self.nodes[node].children[0], l, m, range, line);
+        let right = self.add_range(self.nodes[node].children[1], m + 1, r, range, line);
+        self.nodes[node].children = [left, right];
+        Some(node)
+    }
+
+    /// Adds a segment `y = a * x + b` for `x` in `range`.
+    pub fn add_segment<R: RangeBounds<i64>>(&mut self, a: i64, b: i64, range: R) {
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l.saturating_add(1),
+            Unbounded => self.lo,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r,
+            Excluded(&r) => r.saturating_sub(1),
+            Unbounded => self.hi,
+        };
+        if l <= r {
+            self.root = self.add_range(self.root, self.lo, self.hi, (l, r), (a, b));
+        }
+    }
+
+    /// Returns the minimum or maximum of the lines at `x`, or `M::id()` if there is no line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of the range given at the construction.
+    pub fn query(&self, x: i64) -> i64 {
+        assert!(self.lo <= x && x <= self.hi);
+        let (mut l, mut r) = (self.lo, self.hi);
+        let mut node = self.root;
+        let mut ret = M::id();
+        while let Some(k) = node {
+            let Node { line, children } = &self.nodes[k];
+            ret = M::operate(&ret, &eval(line, x));
+            let m = mid(l, r);
+            node = if x <= m {
+                r = m;
+                children[0]
+            } else {
+                l = m + 1;
+                children[1]
+            };
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynamicLiChaoTree, LiChaoTree};
+    use crate::math::num::alge_struct::types::{MaxMonoid, MinMonoid};
+
+    fn lines() -> Vec<(i64, i64, i64, i64)> {
+        // (a, b, l, r) of the segments on `l..r`
+        (0..60_i64)
+            .map(|i| {
+                let l = (i * 37 % 41) - 20;
+                (i * 13 % 19 - 9, i * 29 % 31 - 15, l, l + i * 7 % 23)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn li_chao_tree() {
+        let xs = (-25..25).map(|x| x * 3 % 50).collect::<Vec<i64>>();
+        let mut min = LiChaoTree::<MinMonoid<i64>>::new(xs.clone());
+        let mut max = LiChaoTree::<MaxMonoid<i64>>::new(xs.clone());
+        let mut added = vec![];
+        for (i, (a, b, l, r)) in lines().into_iter().enumerate() {
+            if i % 3 == 0 {
+                min.add_line(a, b);
+                max.add_line(a, b);
+                added.push((a, b, i64::min_value(), i64::max_value()));
+            } else {
+                min.add_segment(a, b, l..r);
+                max.add_segment(a, b, l..r);
+                added.push((a, b, l, r));
+            }
+            for &x in &xs {
+                let values = added
+                    .iter()
+                    .filter(|&&(_, _, l, r)| l <= x && x < r)
+                    .map(|&(a, b, _, _)| a * x + b);
+                assert_eq!(
+                    min.query(x),
+                    values.clone().min().unwrap_or(i64::max_value())
+                );
+                assert_eq!(max.query(x), values.max().unwrap_or(i64::min_value()));
+            }
+        }
+    }
+
+    #[test]
+    fn dynamic_li_chao_tree() {
+        let mut min = DynamicLiChaoTree::<MinMonoid<i64>>::new(-30..30);
+        let mut max = DynamicLiChaoTree::<MaxMonoid<i64>>::new(-30..=29);
+        let mut added = vec![];
+        for (i, (a, b, l, r)) in lines().into_iter().enumerate() {
+            if i % 3 == 0 {
+                min.add_line(a, b);
+                max.add_line(a, b);
+                added.push((a, b, -30, 30));
+            } else {
+                min.add_segment(a, b, l..r);
+                max.add_segment(a, b, l..r);
+                added.push((a, b, l, r));
+            }
+            for x in -30..30 {
+                let values = added
+                    .iter()
+                    .filter(|&&(_, _, l, r)| l <= x && x < r)
+                    .map(|&(a, b, _, _)| a * x + b);
+                assert_eq!(
+                    min.query(x),
+                    values.clone().min().unwrap_or(i64::max_value())
+                );
+                assert_eq!(max.query(x), values.max().unwrap_or(i64::min_value()));
+            }
+        }
+    }
+
+    #[test]
+    fn large_coordinates() {
+        let mut tree = DynamicLiChaoTree::<MinMonoid<i64>>::new(..);
+        tree.add_line(0, 5);
+        tree.add_segment(1, 0, -1_000_000_000_000_000_000..=1_000_000_000_000_000_000);
+        assert_eq!(
+            tree.query(-1_000_000_000_000_000_000),
+            -1_000_000_000_000_000_000
+        );
+        assert_eq!(tree.query(1_000_000_000_000_000_000), 5);
+        assert_eq!(tree.query(i64::min_value()), 5);
+        assert_eq!(tree.query(i64::max_value()), 5);
+    }
+}
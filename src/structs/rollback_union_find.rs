@@ -0,0 +1,158 @@
+/// A union-find whose unions can be undone in LIFO order.
+///
+/// Unions are by size without path compression, so `find` is O(log(*n*)).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::rollback_union_find::RollbackUnionFind;
+///
+/// let mut uf = RollbackUnionFind::new(4);
+/// uf.union(0, 1);
+/// let snapshot = uf.snapshot();
+/// uf.union(1, 2);
+/// uf.union(2, 3);
+/// assert_eq!(uf.size(0), 4);
+/// uf.rollback();
+/// assert_eq!(uf.size(0), 3);
+/// uf.rollback_to(snapshot);
+/// assert!(uf.equiv(0, 1));
+/// assert!(!uf.equiv(0, 2));
+/// ```
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    // the roots merged by each union, `None` if nothing has changed
+    history: Vec<Option<(usize, usize)>>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(n: usize) -> Self {
+        let parent = (0..n).collect::<Vec<_>>();
+        let size = vec![1; n];
+        Self {
+            parent,
+            size,
+            history: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn find(&self, x: usize) -> usize {
+        assert!(x < self.parent.len());
+        let mut x = x;
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn equiv(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// Merges the groups of `a` and `b`, and returns whether they were different.
+    ///
+    /// Every call can be undone by [`rollback`](Self::rollback), even if it returns `false`.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            self.history.push(None);
+            return false;
+        }
+
+        let (child, root) = if self.size[a] > self.size[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.history.push(Some((child, root)));
+        true
+    }
+
+    /// Returns the state to roll back with [`rollback_to`](Self::rollback_to).
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes the last union, and returns whether there was a union to undo.
+    pub fn rollback(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Some((child, root))) => {
+                self.parent[child] = child;
+                self.size[root] -= self.size[child];
+                true
+            }
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    /// Undoes the unions after `snapshot`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the unions at `snapshot` have been already undone.
+    pub fn rollback_to(&mut self, snapshot: usize) {
+        assert!(snapshot <= self.history.len());
+        while self.history.len() > snapshot {
+            self.rollback();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::union_find::UnionFind;
+
+    #[test]
+    fn rollback() {
+        let n = 12;
+        let edges = (0..30).map(|i| (i * 5 % n, i * 7 % n)).collect::<Vec<_>>();
+        let mut uf = RollbackUnionFind::new(n);
+        let mut snapshots = vec![];
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            snapshots.push(uf.snapshot());
+            let mut expected = UnionFind::new(n);
+            for &(a, b) in &edges[..i] {
+                expected.union(a, b);
+            }
+            assert_eq!(uf.union(a, b), !expected.equiv(a, b));
+            expected.union(a, b);
+            for x in 0..n {
+                assert_eq!(uf.size(x), expected.size(x));
+                assert!(uf.equiv(x, expected.find(x)));
+            }
+        }
+        for (i, snapshot) in snapshots.into_iter().enumerate().rev().step_by(3) {
+            uf.rollback_to(snapshot);
+            let mut expected = UnionFind::new(n);
+            for &(a, b) in &edges[..i] {
+                expected.union(a, b);
+            }
+            for a in 0..n {
+                for b in 0..n {
+                    assert_eq!(uf.equiv(a, b), expected.equiv(a, b));
+                }
+            }
+        }
+        uf.rollback_to(0);
+        assert_eq!(uf.size(0), 1);
+        assert!(!uf.rollback());
+    }
+}
@@ -3,11 +3,31 @@ use crate::math::num::Monoid;
 #[cfg_attr(nightly, codesnip::entry("LazySegTree", include("Monoid")))]
 mod lazy;
 #[codesnip::entry("LazySegTree")]
-pub use lazy::{LSTMonoid, LazySegTree};
+pub use lazy::{LSTAction, LSTMonoid, LazySegTree};
 
 #[cfg_attr(nightly, codesnip::entry("SegmentTree", include("Monoid")))]
 mod segtree;
 #[codesnip::entry("SegmentTree")]
 pub use segtree::SegmentTree;
 
+#[cfg_attr(nightly, codesnip::entry("DynamicSegTree", include("LazySegTree")))]
+mod dynamic;
+#[codesnip::entry("DynamicSegTree")]
+pub use dynamic::{DynamicLazySegTree, DynamicSegTree, SegIndex};
+
+#[cfg_attr(nightly, codesnip::entry("PersistentSegTree", include("Monoid")))]
+mod persistent;
+#[codesnip::entry("PersistentSegTree")]
+pub use persistent::PersistentSegTree;
+
+#[cfg_attr(nightly, codesnip::entry("SegTreeBeats"))]
+mod beats;
+#[codesnip::entry("SegTreeBeats")]
+pub use beats::SegTreeBeats;
+
+#[cfg_attr(nightly, codesnip::entry("SegmentTree2D", include("SegmentTree")))]
+mod segtree2d;
+#[codesnip::entry("SegmentTree2D")]
+pub use segtree2d::{CompressedSegTree2D, SegmentTree2D};
+
 pub mod types;
@@ -0,0 +1,404 @@
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+const INF: i64 = core::i64::MAX;
+const NEG_INF: i64 = core::i64::MIN;
+
+#[derive(Clone, Debug)]
+struct Node {
+    sum: i64,
+    // the largest and the strictly second largest value
+    max: [i64; 2],
+    max_cnt: i64,
+    // the smallest and the strictly second smallest value
+    min: [i64; 2],
+    min_cnt: i64,
+    len: i64,
+    add: i64,
+}
+
+impl Node {
+    fn new(value: i64) -> Self {
+        Self {
+            sum: value,
+            max: [value, NEG_INF],
+            max_cnt: 1,
+            min: [value, INF],
+            min_cnt: 1,
+            len: 1,
+            add: 0,
+        }
+    }
+
+    fn merge(lhs: &Self, rhs: &Self) -> Self {
+        let (max, max_cnt) = if lhs.max[0] == rhs.max[0] {
+            (
+                [lhs.max[0], lhs.max[1].max(rhs.max[1])],
+                lhs.max_cnt + rhs.max_cnt,
+            )
+        } else if lhs.max[0] > rhs.max[0] {
+            ([lhs.max[0], lhs.max[1].max(rhs.max[0])], lhs.max_cnt)
+        } else {
+            ([rhs.max[0], rhs.max[1].max(lhs.max[0])], rhs.max_cnt)
+        };
+        let (min, min_cnt) = if lhs.min[0] == rhs.min[0] {
+            (
+                [lhs.min[0], lhs.min[1].min(rhs.min[1])],
+                lhs.min_cnt + rhs.min_cnt,
+            )
+        } else if lhs.min[0] < rhs.min[0] {
+            ([lhs.min[0], lhs.min[1].min(rhs.min[0])], lhs.min_cnt)
+        } else {
+            ([rhs.min[0], rhs.min[1].min(lhs.min[0])], rhs.min_cnt)
+        };
+        Self {
+            sum: lhs.sum + rhs.sum,
+            max,
+            max_cnt,
+            min,
+            min_cnt,
+            len: lhs.len + rhs.len,
+            add: 0,
+        }
+    }
+
+    // requires `self.max[1] < x`
+    fn chmin(&mut self, x: i64) {
+        if self.max[0] <= x {
+            return;
+        }
+        self.sum += (x - self.max[0]) * self.max_cnt;
+        if self.max[0] == self.min[0] {
+            self.min[0] = x;
+        } else if self.max[0] == self.min[1] {
+            self.min[1] = x;
+        }
+        self.max[0] = x;
+    }
+
+    // requires `x < self.min[1]`
+    fn chmax(&mut self, x: i64) {
+        if x <= self.min[0] {
+            return;
+        }
+        self.sum += (x - self.min[0]) * self.min_cnt;
+        if self.min[0] == self.max[0] {
+            self.max[0] = x;
+        } else if self.min[0] == self.max[1] {
+            self.max[1] = x;
+        }
+        self.min[0] = x;
+    }
+
+    fn add(&mut self, x: i64) {
+        self.sum += x * self.len;
+        self.max[0] += x;
+        if self.max[1] != NEG_INF {
+            self.max[1] += x;
+        }
+        self.min[0] += x;
+        if self.min[1] != INF {
+            self.min[1] += x;
+        }
+        self.add += x;
+    }
+}
+
+/// Segment Tree Beats for `i64`.
+///
+/// Supports range chmin, range chmax, range add and range assign
+/// together with range sum, min and max queries.
+///
+/// # Time complexity
+///
+/// | Algorithm | Amortized       |
+/// | --------- | --------------- |
+/// | Update    | O(log(*n*)^2)   |
+/// | Query     | O(log(*n*))     |
+///
+/// Without [`add`] and [`assign`], updates are amortized O(log(*n*)).
+///
+/// [`add`]: SegTreeBeats::add
+/// [`assign`]: SegTreeBeats::assign
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::segment_tree::SegTreeBeats;
+///
+/// let mut segtree = SegTreeBeats::from(vec![5, 1, 4, 2, 3]);
+/// segtree.chmin(.., 3);
+/// assert_eq!(segtree.sum(..), 12);
+/// segtree.chmax(1..4, 3);
+/// assert_eq!(segtree.sum(..), 15);
+/// segtree.add(..2, 10);
+/// assert_eq!(segtree.max(..), 13);
+/// assert_eq!(segtree.min(2..), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SegTreeBeats {
+    n: usize,
+    tree: Vec<Node>,
+}
+
+impl SegTreeBeats {
+    /// Creates a tree of length `n` filled with `0`.
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![0; n])
+    }
+
+    /// Returns the length of the array.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn build(&mut self, k: usize, l: usize, r: usize, v: &[i64]) {
+        if r - l == 1 {
+            self.tree[k] = Node::new(v[l]);
+        } else {
+            let mid = (l + r) / 2;
+            self.build(2 * k + 1, l, mid, v);
+            self.build(2 * k + 2, mid, r, v);
+            self.update(k);
+        }
+    }
+
+    fn update(&mut self, k: usize) {
+        self.tree[k] = Node::merge(&self.tree[2 * k + 1], &self.tree[2 * k + 2]);
+    }
+
+    fn push(&mut self, k: usize) {
+        let Node { max, min, add, .. } = self.tree[k];
+        for c in 2 * k + 1..2 * k + 3 {
+            let child = &mut self.tree[c];
+            if add != 0 {
+                child.add(add);
+            }
+            child.chmin(max[0]);
+            child.chmax(min[0]);
+        }
+        self.tree[k].add = 0;
+    }
+
+    fn range<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.n,
+        };
+        assert!(l <= r);
+        assert!(r <= self.n);
+        (l, r)
+    }
+
+    /// Replaces each `a[i]` in `range` with `min(a[i], x)`.
+    pub fn chmin<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let (l, r) = self.range(range);
+        if l < r {
+            self.chmin_inner(0, 0, self.n, l, r, x);
+        }
+    }
+
+    fn chmin_inner(&mut self, k: usize, l: usize, r: usize, ql: usize, qr: usize, x: i64) {
+        if qr <= l || r <= ql || self.tree[k].max[0] <= x {
+            return;
+        }
+        if ql <= l && r <= qr && self.tree[k].max[1] < x {
+            self.tree[k].chmin(x);
+            return;
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        self.chmin_inner(2 * k + 1, l, mid, ql, qr, x);
+        self.chmin_inner(2 * k + 2, mid, r, ql, qr, x);
+        self.update(k);
+    }
+
+    /// Replaces each `a[i]` in `range` with `max(a[i], x)`.
+    pub fn chmax<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let (l, r) = self.range(range);
+        if l < r {
+            self.chmax_inner(0, 0, self.n, l, r, x);
+        }
+    }
+
+    fn chmax_inner(&mut self, k: usize, l: usize, r: usize, ql: usize, qr: usize, x: i64) {
+        if qr <= l || r <= ql || x <= self.tree[k].min[0] {
+            return;
+        }
+        if ql <= l && r <= qr && x < self.tree[k].min[1] {
+            self.tree[k].chmax(x);
+            return;
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        self.chmax_inner(2 * k + 1, l, mid, ql, qr, x);
+        self.chmax_inner(2 * k + 2, mid, r, ql, qr, x);
+        self.update(k);
+    }
+
+    /// Adds `x` to each `a[i]` in `range`.
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let (l, r) = self.range(range);
+        if l < r {
+            self.add_inner(0, 0, self.n, l, r, x);
+        }
+    }
+
+    fn add_inner(&mut self, k: usize, l: usize, r: usize, ql: usize, qr: usize, x: i64) {
+        if qr <= l || r <= ql {
+            return;
+        }
+        if ql <= l && r <= qr {
+            self.tree[k].add(x);
+            return;
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        self.add_inner(2 * k + 1, l, mid, ql, qr, x);
+        self.add_inner(2 * k + 2, mid, r, ql, qr, x);
+        self.update(k);
+    }
+
+    /// Replaces each `a[i]` in `range` with `x`.
+    pub fn assign<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let (l, r) = self.range(range);
+        self.chmin(l..r, x);
+        self.chmax(l..r, x);
+    }
+
+    fn fold(&mut self, k: usize, l: usize, r: usize, ql: usize, qr: usize) -> Option<Node> {
+        if qr <= l || r <= ql {
+            return None;
+        }
+        if ql <= l && r <= qr {
+            return Some(self.tree[k].clone());
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        match (
+            self.fold(2 * k + 1, l, mid, ql, qr),
+            self.fold(2 * k + 2, mid, r, ql, qr),
+        ) {
+            (Some(a), Some(b)) => Some(Node::merge(&a, &b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn query<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Node> {
+        let (l, r) = self.range(range);
+        self.fold(0, 0, self.n, l, r)
+    }
+
+    /// Returns the sum of `range`.
+    pub fn sum<R: RangeBounds<usize>>(&mut self, range: R) -> i64 {
+        self.query(range).map_or(0, |node| node.sum)
+    }
+
+    /// Returns the minimum of `range`, or `i64::MAX` if `range` is empty.
+    pub fn min<R: RangeBounds<usize>>(&mut self, range: R) -> i64 {
+        self.query(range).map_or(INF, |node| node.min[0])
+    }
+
+    /// Returns the maximum of `range`, or `i64::MIN` if `range` is empty.
+    pub fn max<R: RangeBounds<usize>>(&mut self, range: R) -> i64 {
+        self.query(range).map_or(NEG_INF, |node| node.max[0])
+    }
+
+    /// Returns the value at `index`.
+    pub fn get(&mut self, index: usize) -> i64 {
+        self.sum(index..=index)
+    }
+}
+
+impl From<Vec<i64>> for SegTreeBeats {
+    fn from(v: Vec<i64>) -> Self {
+        let n = v.len();
+        let mut ret = Self {
+            n,
+            tree: vec![Node::new(0); 4 * n.max(1)],
+        };
+        if n > 0 {
+            ret.build(0, 0, n, &v);
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SegTreeBeats;
+
+    #[test]
+    fn random_operations() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut rand = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+
+        for &n in &[1, 2, 7, 16, 33] {
+            let mut v = (0..n).map(|_| rand(41) as i64 - 20).collect::<Vec<_>>();
+            let mut segtree = SegTreeBeats::from(v.clone());
+            for _ in 0..1000 {
+                let l = rand(n as u64 + 1) as usize;
+                let r = rand(n as u64 + 1) as usize;
+                let (l, r) = (l.min(r), l.max(r));
+                let x = rand(41) as i64 - 20;
+                match rand(7) {
+                    0 => {
+                        segtree.chmin(l..r, x);
+                        v[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+                    }
+                    1 => {
+                        segtree.chmax(l..r, x);
+                        v[l..r].iter_mut().for_each(|a| *a = (*a).max(x));
+                    }
+                    2 => {
+                        segtree.add(l..r, x);
+                        v[l..r].iter_mut().for_each(|a| *a += x);
+                    }
+                    3 => {
+                        segtree.assign(l..r, x);
+                        v[l..r].iter_mut().for_each(|a| *a = x);
+                    }
+                    4 => assert_eq!(segtree.sum(l..r), v[l..r].iter().sum::<i64>()),
+                    5 => assert_eq!(
+                        segtree.min(l..r),
+                        v[l..r].iter().copied().min().unwrap_or(core::i64::MAX)
+                    ),
+                    _ => assert_eq!(
+                        segtree.max(l..r),
+                        v[l..r].iter().copied().max().unwrap_or(core::i64::MIN)
+                    ),
+                }
+            }
+            for (i, &a) in v.iter().enumerate() {
+                assert_eq!(segtree.get(i), a);
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let mut segtree = SegTreeBeats::new(0);
+        segtree.chmin(.., 1);
+        segtree.add(.., 1);
+        assert_eq!(segtree.sum(..), 0);
+        assert_eq!(segtree.min(..), core::i64::MAX);
+        assert_eq!(segtree.max(..), core::i64::MIN);
+    }
+}
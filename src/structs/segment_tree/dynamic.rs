@@ -0,0 +1,413 @@
+use super::{LSTAction, Monoid};
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    Range, RangeBounds,
+};
+
+/// An integer type which can be used as an index of [`DynamicSegTree`].
+pub trait SegIndex: Copy + Ord {
+    /// Returns the middle of `l..r`.
+    fn mid(l: Self, r: Self) -> Self;
+
+    /// Returns the next index.
+    fn succ(self) -> Self;
+
+    /// Returns the number of indices in `l..r`.
+    fn len(l: Self, r: Self) -> usize;
+}
+
+macro_rules! impl_seg_index {
+    ($($t:ty)*) => {$(
+        impl SegIndex for $t {
+            fn mid(l: Self, r: Self) -> Self {
+                (l as i128 + (r as i128 - l as i128) / 2) as $t
+            }
+
+            fn succ(self) -> Self {
+                self + 1
+            }
+
+            fn len(l: Self, r: Self) -> usize {
+                (r as i128 - l as i128) as usize
+            }
+        }
+    )*};
+}
+
+impl_seg_index! { i32 i64 isize u32 u64 usize }
+
+fn to_range<I: SegIndex, R: RangeBounds<I>>(range: R, domain: &Range<I>) -> Range<I> {
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l.succ(),
+        Unbounded => domain.start,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r.succ(),
+        Excluded(&r) => r,
+        Unbounded => domain.end,
+    };
+    assert!(domain.start <= l && l <= r && r <= domain.end);
+    l..r
+}
+
+/// A segment tree which allocates the nodes on demand.
+///
+/// It can handle huge index ranges such as `0..10^18`,
+/// and it uses O(*q* log(*n*)) memory where *q* is the number of updates.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::segment_tree::DynamicSegTree;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let mut segtree = DynamicSegTree::<AddMonoid<i64>>::new(0..1_000_000_000_000_000_000);
+/// segtree.set(3, 5);
+/// segtree.set(999_999_999_999_999_999, 7);
+/// assert_eq!(segtree.query(..), 12);
+/// assert_eq!(segtree.query(4..), 7);
+/// ```
+pub struct DynamicSegTree<M: Monoid, I = i64> {
+    domain: Range<I>,
+    // `0` is the root, so it is used as the absence of a child
+    nodes: Vec<(M::Set, [usize; 2])>,
+}
+
+impl<M: Monoid, I: SegIndex> DynamicSegTree<M, I> {
+    /// Creates a tree for the indices in `domain` filled with `M::id()`.
+    pub fn new(domain: Range<I>) -> Self {
+        assert!(domain.start < domain.end);
+        Self {
+            domain,
+            nodes: vec![(M::id(), [0; 2])],
+        }
+    }
+
+    /// Returns the range of the indices.
+    pub fn domain(&self) -> &Range<I> {
+        &self.domain
+    }
+
+    fn child(&mut self, node: usize, side: usize) -> usize {
+        if self.nodes[node].1[side] == 0 {
+            self.nodes.push((M::id(), [0; 2]));
+            self.nodes[node].1[side] = self.nodes.len() - 1;
+        }
+        self.nodes[node].1[side]
+    }
+
+    fn value(&self, child: usize) -> M::Set {
+        match child {
+            0 => M::id(),
+            child => self.nodes[child].0.clone(),
+        }
+    }
+
+    pub fn set(&mut self, index: I, value: M::Set) {
+        assert!(self.domain.start <= index && index < self.domain.end);
+        let (mut l, mut r) = (self.domain.start, self.domain.end);
+        let mut path = vec![0];
+        while I::len(l, r) > 1 {
+            let mid = I::mid(l, r);
+            let node = *path.last().unwrap();
+            if index < mid {
+                path.push(self.child(node, 0));
+                r = mid;
+            } else {
+                path.push(self.child(node, 1));
+                l = mid;
+            }
+        }
+
+        self.nodes[path.pop().unwrap()].0 = value;
+        while let Some(node) = path.pop() {
+            let [left, right] = self.nodes[node].1;
+            self.nodes[node].0 = M::operate(&self.value(left), &self.value(right));
+        }
+    }
+
+    pub fn get(&self, index: I) -> M::Set {
+        self.query(index..=index)
+    }
+
+    pub fn query<R: RangeBounds<I>>(&self, range: R) -> M::Set {
+        let range = to_range(range, &self.domain);
+        self.query_inner(0, self.domain.start, self.domain.end, &range)
+    }
+
+    fn query_inner(&self, node: usize, l: I, r: I, range: &Range<I>) -> M::Set {
+        if range.end <= l || r <= range.start {
+            M::id()
+        } else if range.start <= l && r <= range.end {
+            self.nodes[node].0.clone()
+        } else {
+            let mid = I::mid(l, r);
+            let [left, right] = self.nodes[node].1;
+            let lv = match left {
+                0 => M::id(),
+                left => self.query_inner(left, l, mid, range),
+            };
+            let rv = match right {
+                0 => M::id(),
+                right => self.query_inner(right, mid, r, range),
+            };
+            M::operate(&lv, &rv)
+        }
+    }
+}
+
+/// A [`DynamicSegTree`] with lazy propagation by [`LSTAction`].
+///
+/// Absent nodes are regarded as the fold of `M::id()`,
+/// so `A::act(f, &M::id(), len)` must be the fold of `len` acted identities.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::segment_tree::DynamicLazySegTree;
+/// use lib_rust::structs::segment_tree::types::AddSum;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let mut segtree = DynamicLazySegTree::<AddMonoid<i64>, AddSum<i64>>::new(-1_000_000_000..1_000_000_000);
+/// segtree.set(-5, 3);
+/// segtree.set(7, 4);
+/// // adds 2 to each of `0..10`
+/// segtree.operate(0..10, 2);
+/// assert_eq!(segtree.query(..), 27);
+/// assert_eq!(segtree.query(..0), 3);
+/// assert_eq!(segtree.query(5..8), 10);
+/// assert_eq!(segtree.get(7), 6);
+/// ```
+pub struct DynamicLazySegTree<M: Monoid, A: LSTAction<M>, I = i64> {
+    domain: Range<I>,
+    // `0` is the root, so it is used as the absence of a child
+    nodes: Vec<(M::Set, A::Act, [usize; 2])>,
+}
+
+impl<M: Monoid, A: LSTAction<M>, I: SegIndex> DynamicLazySegTree<M, A, I> {
+    /// Creates a tree for the indices in `domain` filled with `M::id()`.
+    pub fn new(domain: Range<I>) -> Self {
+        assert!(domain.start < domain.end);
+        Self {
+            domain,
+            nodes: vec![(M::id(), A::id_act(), [0; 2])],
+        }
+    }
+
+    /// Returns the range of the indices.
+    pub fn domain(&self) -> &Range<I> {
+        &self.domain
+    }
+
+    fn child(&mut self, node: usize, side: usize) -> usize {
+        if self.nodes[node].2[side] == 0 {
+            self.nodes.push((M::id(), A::id_act(), [0; 2]));
+            self.nodes[node].2[side] = self.nodes.len() - 1;
+        }
+        self.nodes[node].2[side]
+    }
+
+    fn apply(&mut self, node: usize, acter: &A::Act, len: usize) {
+        let (value, lazy, _) = &mut self.nodes[node];
+        *value = A::act(acter, value, len);
+        *lazy = A::merge_act(lazy, acter);
+    }
+
+    fn eval(&mut self, node: usize, l: I, r: I) {
+        if A::is_id_act(&self.nodes[node].1) || I::len(l, r) <= 1 {
+            return;
+        }
+        let acter = core::mem::replace(&mut self.nodes[node].1, A::id_act());
+        let mid = I::mid(l, r);
+        let left = self.child(node, 0);
+        self.apply(left, &acter, I::len(l, mid));
+        let right = self.child(node, 1);
+        self.apply(right, &acter, I::len(mid, r));
+    }
+
+    fn update(&mut self, node: usize) {
+        let [left, right] = self.nodes[node].2;
+        let lv = match left {
+            0 => M::id(),
+            left => self.nodes[left].0.clone(),
+        };
+        let rv = match right {
+            0 => M::id(),
+            right => self.nodes[right].0.clone(),
+        };
+        self.nodes[node].0 = M::operate(&lv, &rv);
+    }
+
+    pub fn set(&mut self, index: I, value: M::Set) {
+        assert!(self.domain.start <= index && index < self.domain.end);
+        let (mut l, mut r) = (self.domain.start, self.domain.end);
+        let mut path = vec![0];
+        while I::len(l, r) > 1 {
+            let node = *path.last().unwrap();
+            self.eval(node, l, r);
+            let mid = I::mid(l, r);
+            if index < mid {
+                path.push(self.child(node, 0));
+                r = mid;
+            } else {
+                path.push(self.child(node, 1));
+                l = mid;
+            }
+        }
+
+        let leaf = path.pop().unwrap();
+        self.nodes[leaf].0 = value;
+        self.nodes[leaf].1 = A::id_act();
+        while let Some(node) = path.pop() {
+            self.update(node);
+        }
+    }
+
+    pub fn get(&mut self, index: I) -> M::Set {
+        self.query(index..=index)
+    }
+
+    pub fn query<R: RangeBounds<I>>(&mut self, range: R) -> M::Set {
+        let range = to_range(range, &self.domain);
+        let (l, r) = (self.domain.start, self.domain.end);
+        self.query_inner(0, l, r, &range)
+    }
+
+    fn query_inner(&mut self, node: usize, l: I, r: I, range: &Range<I>) -> M::Set {
+        if range.end <= l || r <= range.start {
+            M::id()
+        } else if range.start <= l && r <= range.end {
+            self.nodes[node].0.clone()
+        } else {
+            self.eval(node, l, r);
+            let mid = I::mid(l, r);
+            let [left, right] = self.nodes[node].2;
+            let lv = match left {
+                0 => M::id(),
+                left => self.query_inner(left, l, mid, range),
+            };
+            let rv = match right {
+                0 => M::id(),
+                right => self.query_inner(right, mid, r, range),
+            };
+            M::operate(&lv, &rv)
+        }
+    }
+
+    pub fn operate<R: RangeBounds<I>, T: Into<A::Act>>(&mut self, range: R, value: T) {
+        let range = to_range(range, &self.domain);
+        let (l, r) = (self.domain.start, self.domain.end);
+        self.operate_inner(0, l, r, &range, &value.into());
+    }
+
+    fn operate_inner(&mut self, node: usize, l: I, r: I, range: &Range<I>, acter: &A::Act) {
+        if range.end <= l || r <= range.start {
+            return;
+        }
+        if range.start <= l && r <= range.end {
+            self.apply(node, acter, I::len(l, r));
+            return;
+        }
+        self.eval(node, l, r);
+        let mid = I::mid(l, r);
+        let left = self.child(node, 0);
+        self.operate_inner(left, l, mid, range, acter);
+        let right = self.child(node, 1);
+        self.operate_inner(right, mid, r, range, acter);
+        self.update(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::types::{AddSum, LMRep};
+    use super::{DynamicLazySegTree, DynamicSegTree};
+    use crate::math::num::alge_struct::types::{AddMonoid, MaxMonoid};
+    use crate::math::num::{Monoid, Semigroup};
+
+    const NAPIER: [i64; 12] = [2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 6];
+
+    #[test]
+    fn query() {
+        for &offset in &[-6, 0, 5] {
+            let domain = offset..offset + NAPIER.len() as i64;
+            let mut segtree = DynamicSegTree::<MaxMonoid<i64>>::new(domain.clone());
+            let mut ans = vec![MaxMonoid::<i64>::id(); NAPIER.len()];
+            for (i, &x) in NAPIER.iter().enumerate() {
+                segtree.set(offset + i as i64, x);
+                ans[i] = x;
+                for l in domain.clone() {
+                    for r in l..=domain.end {
+                        let fold = ans[(l - offset) as usize..(r - offset) as usize]
+                            .iter()
+                            .fold(MaxMonoid::<i64>::id(), |a, b| MaxMonoid::operate(&a, b));
+                        assert_eq!(segtree.query(l..r), fold, "range: `{}..{}`", l, r);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_domain() {
+        const N: u64 = 1_000_000_000_000_000_000;
+        let mut segtree = DynamicSegTree::<AddMonoid<u64>, u64>::new(0..N);
+        let indices = [0, 1, N / 3, N / 2, N - 2, N - 1];
+        for (i, &k) in indices.iter().enumerate() {
+            segtree.set(k, i as u64 + 1);
+        }
+        for (i, &k) in indices.iter().enumerate() {
+            assert_eq!(segtree.get(k), i as u64 + 1);
+            assert_eq!(segtree.query(..=k), (1..=i as u64 + 1).sum::<u64>());
+            assert_eq!(
+                segtree.query(k..),
+                (i as u64 + 1..=indices.len() as u64).sum::<u64>()
+            );
+        }
+        assert_eq!(segtree.get(N / 4), 0);
+        assert!(segtree.nodes.len() <= indices.len() * 64);
+    }
+
+    #[test]
+    fn lazy_add_sum() {
+        let domain = -7..7;
+        let mut segtree = DynamicLazySegTree::<AddMonoid<i64>, AddSum<i64>>::new(domain.clone());
+        let mut ans = [0; 14];
+        for l in domain.clone() {
+            for r in l..=domain.end {
+                let x = (r - l) % 5 - 2;
+                segtree.operate(l..r, x);
+                for a in &mut ans[(l + 7) as usize..(r + 7) as usize] {
+                    *a += x;
+                }
+                if (l + r) % 3 == 0 {
+                    segtree.set(l, l * r);
+                    ans[(l + 7) as usize] = l * r;
+                }
+                for ql in domain.clone() {
+                    for qr in ql..=domain.end {
+                        let sum = ans[(ql + 7) as usize..(qr + 7) as usize]
+                            .iter()
+                            .sum::<i64>();
+                        assert_eq!(segtree.query(ql..qr), sum, "range: `{}..{}`", ql, qr);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_huge_domain() {
+        const N: i64 = 1_000_000_000_000_000_000;
+        let mut segtree = DynamicLazySegTree::<AddMonoid<i64>, LMRep<AddMonoid<i64>>>::new(-N..N);
+        segtree.operate(.., 1);
+        assert_eq!(segtree.query(..), 2 * N);
+        segtree.operate(-5..5, 3);
+        assert_eq!(segtree.query(-10..10), 10 + 30);
+        segtree.set(0, -100);
+        assert_eq!(segtree.query(-10..10), 10 + 27 - 100);
+        assert_eq!(segtree.get(4), 3);
+        assert_eq!(segtree.query(..), 2 * N - 10 + 27 - 100);
+    }
+}
@@ -3,17 +3,20 @@ use core::fmt;
 use core::marker::PhantomData;
 use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
+mod lst_action;
+pub use lst_action::LSTAction;
+
 // #[cfg_attr(nightly, codesnip::entry("LSTMonoid", include("Monoid")))]
 mod lst_monoid;
 // #[codesnip::entry("LSTMonoid")]
 pub use lst_monoid::LSTMonoid;
 
-pub struct LazySegTree<M: Monoid, LM: LSTMonoid<M>> {
+pub struct LazySegTree<M: Monoid, A: LSTAction<M>> {
     len: usize,
     depth: usize,
     tree: Vec<M::Set>,
-    lazy: Vec<Option<M::Set>>,
-    lst_monoid: PhantomData<LM>,
+    lazy: Vec<A::Act>,
+    action: PhantomData<A>,
 }
 
 // if i == 0 { 0 } else { log2(i).floor() + 1 }
@@ -21,7 +24,7 @@ fn bit(i: usize) -> usize {
     (64 - (i as u64).leading_zeros()) as usize
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LazySegTree<M, LM> {
+impl<M: Monoid, A: LSTAction<M>> LazySegTree<M, A> {
     pub fn new(min_len: usize) -> Self {
         Self::init(min_len, &vec![])
     }
@@ -33,7 +36,7 @@ impl<M: Monoid, LM: LSTMonoid<M>> LazySegTree<M, LM> {
         let len = 1 << depth;
         let size = 2 * len - 1;
         let mut tree = vec![M::id(); size];
-        let lazy = vec![LM::id_act(); size];
+        let lazy = vec![A::id_act(); size];
 
         // clone
         tree[len - 1..len - 1 + v.len()].clone_from_slice(v);
@@ -50,7 +53,7 @@ impl<M: Monoid, LM: LSTMonoid<M>> LazySegTree<M, LM> {
             depth,
             tree,
             lazy,
-            lst_monoid: PhantomData,
+            action: PhantomData,
         }
     }
 
@@ -68,32 +71,32 @@ impl<M: Monoid, LM: LSTMonoid<M>> LazySegTree<M, LM> {
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LazySegTree<M, LM> {
+impl<M: Monoid, A: LSTAction<M>> LazySegTree<M, A> {
     fn eval(&mut self, i: usize) {
-        if !LM::is_id_act(&self.lazy[i]) {
-            let acter = &LM::drain(&mut self.lazy[i]);
+        if !A::is_id_act(&self.lazy[i]) {
+            let acter = &core::mem::replace(&mut self.lazy[i], A::id_act());
 
-            self.tree[i] = LM::act(&self.tree[i], acter, self.range(i));
+            self.tree[i] = A::act(acter, &self.tree[i], self.range(i));
 
             if i < self.len() - 1 {
                 let left = &mut self.lazy[i * 2 + 1];
-                *left = LM::merge_act(left, acter);
+                *left = A::merge_act(left, acter);
 
                 let right = &mut self.lazy[i * 2 + 2];
-                *right = LM::merge_act(right, acter);
+                *right = A::merge_act(right, acter);
             }
         }
     }
 
-    pub fn get<I: LSTIndex<M, LM>>(&mut self, index: I) -> Option<M::Set> {
+    pub fn get<I: LSTIndex<M, A>>(&mut self, index: I) -> Option<M::Set> {
         LSTIndex::get(index, self)
     }
 
-    pub unsafe fn get_unchecked<I: LSTIndex<M, LM>>(&mut self, index: I) -> M::Set {
+    pub unsafe fn get_unchecked<I: LSTIndex<M, A>>(&mut self, index: I) -> M::Set {
         LSTIndex::get_unchecked(index, self)
     }
 
-    pub fn operate<I: LSTIndex<M, LM>, T: Into<Option<M::Set>>>(&mut self, index: I, value: T) {
+    pub fn operate<I: LSTIndex<M, A>, T: Into<A::Act>>(&mut self, index: I, value: T) {
         LSTIndex::operate(index, self, &value.into())
     }
 
@@ -115,14 +118,14 @@ impl<M: Monoid, LM: LSTMonoid<M>> LazySegTree<M, LM> {
     }
 }
 
-pub trait LSTIndex<M: Monoid, LM: LSTMonoid<M>> {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set>;
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set;
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>);
+pub trait LSTIndex<M: Monoid, A: LSTAction<M>> {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set>;
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set;
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act);
 }
 
-fn get_unchecked_inner<M: Monoid, LM: LSTMonoid<M>>(
-    lst: &mut LazySegTree<M, LM>,
+fn get_unchecked_inner<M: Monoid, A: LSTAction<M>>(
+    lst: &mut LazySegTree<M, A>,
     range: &Range<usize>,
     i: usize,
     l: usize,
@@ -147,8 +150,8 @@ fn get_unchecked_inner<M: Monoid, LM: LSTMonoid<M>>(
 
 #[codesnip::skip]
 #[allow(dead_code)]
-fn get_non_recursive<M: Monoid, LM: LSTMonoid<M>>(
-    lst: &mut LazySegTree<M, LM>,
+fn get_non_recursive<M: Monoid, A: LSTAction<M>>(
+    lst: &mut LazySegTree<M, A>,
     range: Range<usize>,
 ) -> M::Set {
     let mut lacc = M::id();
@@ -172,17 +175,17 @@ fn get_non_recursive<M: Monoid, LM: LSTMonoid<M>>(
     M::operate(&lacc, &racc)
 }
 
-fn operate_inner<M: Monoid, LM: LSTMonoid<M>>(
-    lst: &mut LazySegTree<M, LM>,
+fn operate_inner<M: Monoid, A: LSTAction<M>>(
+    lst: &mut LazySegTree<M, A>,
     range: &Range<usize>,
-    val: &Option<M::Set>,
+    val: &A::Act,
     i: usize,
     l: usize,
     r: usize,
 ) {
     if range.contains(&l) && (range.start..=range.end).contains(&r) {
         // if `l..r` in `range`
-        lst.lazy[i] = LM::merge_act(&lst.lazy[i], &val);
+        lst.lazy[i] = A::merge_act(&lst.lazy[i], val);
         lst.eval(i);
     } else if (..range.end).contains(&l) && (..r).contains(&range.start) {
         // if `range` and `l..r` have a common range
@@ -193,15 +196,18 @@ fn operate_inner<M: Monoid, LM: LSTMonoid<M>>(
         operate_inner(lst, range, val, li, l, mid);
         operate_inner(lst, range, val, ri, mid, r);
         lst.tree[i] = M::operate(&lst.tree[li], &lst.tree[ri]);
+    } else {
+        // if `l..r` out of `range`, the pending action is applied to be read by the parent
+        lst.eval(i);
     }
 }
 
 #[codesnip::skip]
 #[allow(dead_code)]
-fn operate_non_recursive<M: Monoid, LM: LSTMonoid<M>>(
-    lst: &mut LazySegTree<M, LM>,
+fn operate_non_recursive<M: Monoid, A: LSTAction<M>>(
+    lst: &mut LazySegTree<M, A>,
     range: Range<usize>,
-    value: &Option<M::Set>,
+    value: &A::Act,
 ) {
     let mut left = range.start + lst.len() - 1;
     let mut right = range.end + lst.len() - 1;
@@ -209,12 +215,12 @@ fn operate_non_recursive<M: Monoid, LM: LSTMonoid<M>>(
     while left < right {
         if left & 1 == 0 {
             lst.eval(left);
-            lst.lazy[left] = LM::merge_act(&lst.lazy[left], value);
+            lst.lazy[left] = A::merge_act(&lst.lazy[left], value);
         }
         if right & 1 == 0 {
             right -= 1;
             lst.eval(right);
-            lst.lazy[right] = LM::merge_act(&lst.lazy[right], value);
+            lst.lazy[right] = A::merge_act(&lst.lazy[right], value);
         }
 
         left >>= 1;
@@ -222,8 +228,8 @@ fn operate_non_recursive<M: Monoid, LM: LSTMonoid<M>>(
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for Range<usize> {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for Range<usize> {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         if self.end <= lst.len() {
             unsafe { Some(self.get_unchecked(lst)) }
         } else {
@@ -231,111 +237,112 @@ impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for Range<usize> {
         }
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         get_unchecked_inner(lst, &self, 0, 0, lst.len())
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         assert!(self.end <= lst.len());
         operate_inner(lst, &self, value, 0, 0, lst.len())
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for RangeInclusive<usize> {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for RangeInclusive<usize> {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         LSTIndex::get(*self.start()..self.end() + 1, lst)
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         LSTIndex::get_unchecked(*self.start()..self.end() + 1, lst)
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         LSTIndex::operate(*self.start()..self.end() + 1, lst, value)
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for RangeFull {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for RangeFull {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         LSTIndex::get(0..lst.len(), lst)
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         LSTIndex::get_unchecked(0..lst.len(), lst)
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         LSTIndex::operate(0..lst.len(), lst, value)
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for RangeFrom<usize> {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for RangeFrom<usize> {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         LSTIndex::get(self.start..lst.len(), lst)
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         LSTIndex::get_unchecked(self.start..lst.len(), lst)
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         LSTIndex::operate(self.start..lst.len(), lst, value)
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for RangeTo<usize> {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for RangeTo<usize> {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         LSTIndex::get(0..self.end, lst)
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         LSTIndex::get_unchecked(0..self.end, lst)
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         LSTIndex::operate(0..self.end, lst, value)
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for RangeToInclusive<usize> {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for RangeToInclusive<usize> {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         LSTIndex::get(0..=self.end, lst)
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         LSTIndex::get_unchecked(0..=self.end, lst)
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         LSTIndex::operate(0..=self.end, lst, value)
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> LSTIndex<M, LM> for usize {
-    fn get(self, lst: &mut LazySegTree<M, LM>) -> Option<M::Set> {
+impl<M: Monoid, A: LSTAction<M>> LSTIndex<M, A> for usize {
+    fn get(self, lst: &mut LazySegTree<M, A>) -> Option<M::Set> {
         LSTIndex::get(self..=self, lst)
     }
 
-    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, LM>) -> M::Set {
+    unsafe fn get_unchecked(self, lst: &mut LazySegTree<M, A>) -> M::Set {
         LSTIndex::get_unchecked(self..=self, lst)
     }
 
-    fn operate(self, lst: &mut LazySegTree<M, LM>, value: &Option<M::Set>) {
+    fn operate(self, lst: &mut LazySegTree<M, A>, value: &A::Act) {
         LSTIndex::operate(self..=self, lst, value)
     }
 }
 
-impl<M: Monoid, LM: LSTMonoid<M>> From<Vec<M::Set>> for LazySegTree<M, LM> {
+impl<M: Monoid, A: LSTAction<M>> From<Vec<M::Set>> for LazySegTree<M, A> {
     fn from(v: Vec<M::Set>) -> Self {
         Self::init(v.len(), &v)
     }
 }
 
-impl<M, LM> fmt::Debug for LazySegTree<M, LM>
+impl<M, A> fmt::Debug for LazySegTree<M, A>
 where
     M: Monoid,
     M::Set: fmt::Debug,
-    LM: LSTMonoid<M>,
+    A: LSTAction<M>,
+    A::Act: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for i in self.tree_index() {
@@ -358,8 +365,8 @@ where
             }
 
             write!(f, "{:?}", self.tree[i])?;
-            if let Some(lazy) = &self.lazy[i] {
-                write!(f, "[{:?}]", lazy)?;
+            if !A::is_id_act(&self.lazy[i]) {
+                write!(f, "[{:?}]", self.lazy[i])?;
             }
             writeln!(f)?;
         }
@@ -367,11 +374,12 @@ where
     }
 }
 
-impl<M, LM> fmt::Display for LazySegTree<M, LM>
+impl<M, A> fmt::Display for LazySegTree<M, A>
 where
     M: Monoid,
     M::Set: fmt::Display,
-    LM: LSTMonoid<M>,
+    A: LSTAction<M>,
+    A::Act: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for i in self.tree_index() {
@@ -392,8 +400,8 @@ where
             }
 
             write!(f, "{}", self.tree[i])?;
-            if let Some(lazy) = &self.lazy[i] {
-                write!(f, "[{}]", lazy)?;
+            if !A::is_id_act(&self.lazy[i]) {
+                write!(f, "[{:?}]", self.lazy[i])?;
             }
             writeln!(f)?;
         }
@@ -472,12 +480,115 @@ mod tests {
         }
     }
 
+    // `Act::act` is ambiguous between `LSTMonoid` and `LSTAction` in the parent module
+    mod action {
+        use super::super::LSTAction;
+        use super::{check_segtree, LazySegTree, Monoid, Semigroup};
+        use crate::math::num::alge_struct::types::MaxMonoid;
+
+        const MOD: u64 = 998_244_353;
+
+        // Sum monoid with the length of the range
+        struct SumLen;
+        impl Semigroup for SumLen {
+            type Set = (u64, u64);
+
+            fn operate(lhs: &Self::Set, rhs: &Self::Set) -> Self::Set {
+                ((lhs.0 + rhs.0) % MOD, lhs.1 + rhs.1)
+            }
+        }
+        impl Monoid for SumLen {
+            fn id() -> Self::Set {
+                (0, 0)
+            }
+        }
+
+        // Affine transform `x -> a * x + b`
+        struct Affine;
+        impl LSTAction<SumLen> for Affine {
+            type Act = (u64, u64);
+
+            fn id_act() -> Self::Act {
+                (1, 0)
+            }
+
+            fn is_id_act(act: &Self::Act) -> bool {
+                *act == (1, 0)
+            }
+
+            fn merge_act(lhs: &Self::Act, rhs: &Self::Act) -> Self::Act {
+                (lhs.0 * rhs.0 % MOD, (lhs.1 * rhs.0 + rhs.1) % MOD)
+            }
+
+            fn act(acter: &Self::Act, set: &(u64, u64), _len: usize) -> (u64, u64) {
+                ((acter.0 * set.0 + acter.1 * set.1) % MOD, set.1)
+            }
+        }
+
+        #[test]
+        fn affine() {
+            let len = 13;
+            let mut ans = (0..len as u64).map(|x| (x, 1)).collect::<Vec<_>>();
+            let mut segtree = LazySegTree::<SumLen, Affine>::from(ans.clone());
+            for i in 0..len {
+                for k in i..=len {
+                    let val = ((k - i) as u64 % 5 + 1, (i * k) as u64 % 7);
+                    for x in &mut ans[i..k] {
+                        *x = Affine::act(&val, x, 1);
+                    }
+                    segtree.operate(i..k, val);
+                    check_segtree(&ans, &mut segtree);
+                }
+            }
+        }
+
+        // Range chmin on max
+        struct Chmin;
+        impl LSTAction<MaxMonoid<i64>> for Chmin {
+            type Act = i64;
+
+            fn id_act() -> Self::Act {
+                core::i64::MAX
+            }
+
+            fn is_id_act(act: &Self::Act) -> bool {
+                *act == core::i64::MAX
+            }
+
+            fn merge_act(lhs: &Self::Act, rhs: &Self::Act) -> Self::Act {
+                *lhs.min(rhs)
+            }
+
+            fn act(acter: &Self::Act, set: &i64, _len: usize) -> i64 {
+                *acter.min(set)
+            }
+        }
+
+        #[test]
+        fn chmin_max() {
+            let len = 14;
+            let mut ans = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 5, 9, 0];
+            let mut segtree = LazySegTree::<MaxMonoid<i64>, Chmin>::from(ans.clone());
+            for i in 0..len {
+                for k in i..=len {
+                    let val = 9 - ((k - i) as i64 % 7);
+                    for x in &mut ans[i..k] {
+                        *x = Chmin::act(&val, x, 1);
+                    }
+                    segtree.operate(i..k, val);
+                    check_segtree(&ans, &mut segtree);
+                }
+            }
+        }
+    }
+
     #[allow(dead_code)]
-    fn check_segtree<M, LM>(ans: &[M::Set], segtree: &mut LazySegTree<M, LM>)
+    fn check_segtree<M, A>(ans: &[M::Set], segtree: &mut LazySegTree<M, A>)
     where
         M: Monoid,
         M::Set: PartialEq + Debug,
-        LM: LSTMonoid<M>,
+        A: super::LSTAction<M>,
+        A::Act: Debug,
     {
         let n = ans.len();
         // get for each
@@ -0,0 +1,31 @@
+use super::Monoid;
+
+/// An action on the values of [`LazySegTree`](super::LazySegTree).
+///
+/// The set of actions [`Act`](LSTAction::Act) must be a monoid
+/// under [`merge_act`](LSTAction::merge_act) with identity [`id_act`](LSTAction::id_act),
+/// and [`act`](LSTAction::act) must distribute over [`Semigroup::operate`](crate::math::num::Semigroup::operate).
+///
+/// ~~~text
+/// act(f, a ◦ b, n + m) = act(f, a, n) ◦ act(f, b, m)
+/// act(merge_act(f, g), a, n) = act(g, act(f, a, n), n)
+/// ~~~
+pub trait LSTAction<M: Monoid> {
+    type Act: Clone;
+
+    /// Returns the identity action, which does not change any value.
+    fn id_act() -> Self::Act;
+
+    /// Returns `true` if `act` is the identity action.
+    ///
+    /// It is only used to skip the propagation, so returning `false` is always correct.
+    fn is_id_act(_act: &Self::Act) -> bool {
+        false
+    }
+
+    /// Composes the actions, `lhs` is applied first and then `rhs` is applied.
+    fn merge_act(lhs: &Self::Act, rhs: &Self::Act) -> Self::Act;
+
+    /// Applies `acter` to `set`, which is the fold of `len` elements.
+    fn act(acter: &Self::Act, set: &M::Set, len: usize) -> M::Set;
+}
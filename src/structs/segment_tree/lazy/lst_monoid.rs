@@ -1,4 +1,4 @@
-use super::Monoid;
+use super::{LSTAction, Monoid};
 
 pub trait LSTMonoid<M: Monoid> {
     fn id_act() -> Option<M::Set> {
@@ -19,3 +19,24 @@ pub trait LSTMonoid<M: Monoid> {
 
     fn merge_act(lhs: &Option<M::Set>, rhs: &Option<M::Set>) -> Option<M::Set>;
 }
+
+/// Adapts the `Option` based [`LSTMonoid`] to [`LSTAction`].
+impl<M: Monoid, LM: LSTMonoid<M>> LSTAction<M> for LM {
+    type Act = Option<M::Set>;
+
+    fn id_act() -> Self::Act {
+        <LM as LSTMonoid<M>>::id_act()
+    }
+
+    fn is_id_act(act: &Self::Act) -> bool {
+        <LM as LSTMonoid<M>>::is_id_act(act)
+    }
+
+    fn merge_act(lhs: &Self::Act, rhs: &Self::Act) -> Self::Act {
+        <LM as LSTMonoid<M>>::merge_act(lhs, rhs)
+    }
+
+    fn act(acter: &Self::Act, set: &M::Set, len: usize) -> M::Set {
+        <LM as LSTMonoid<M>>::act(set, acter, len)
+    }
+}
@@ -0,0 +1,235 @@
+use super::Monoid;
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+/// A fully persistent segment tree.
+///
+/// Each [`set`] creates a new version which shares the unchanged nodes with the old one,
+/// and any version can be queried afterwards.
+/// The version `0` is the initial array.
+///
+/// # Time complexity
+///
+/// | Algorithm | Worst case  |
+/// | --------- | ----------- |
+/// | Memory    | O(*n* + *q* log(*n*)) |
+/// | [`set`]   | O(log(*n*)) |
+/// | [`query`] | O(log(*n*)) |
+///
+/// [`set`]: PersistentSegTree::set
+/// [`query`]: PersistentSegTree::query
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::segment_tree::PersistentSegTree;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let mut segtree = PersistentSegTree::<AddMonoid<i32>>::from(vec![1, 2, 3]);
+/// let v1 = segtree.set(0, 1, 10);
+/// let v2 = segtree.set(v1, 2, 20);
+/// assert_eq!(segtree.query(0, ..), 6);
+/// assert_eq!(segtree.query(v1, ..), 14);
+/// assert_eq!(segtree.query(v2, 1..), 30);
+/// ```
+pub struct PersistentSegTree<M: Monoid> {
+    n: usize,
+    // `0` is the node of `M::id()` whose children are itself
+    nodes: Vec<(M::Set, [usize; 2])>,
+    roots: Vec<usize>,
+}
+
+impl<M: Monoid> PersistentSegTree<M> {
+    /// Creates a tree of length `n` filled with `M::id()`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            nodes: vec![(M::id(), [0; 2])],
+            roots: vec![0],
+        }
+    }
+
+    fn build(&mut self, v: &[M::Set]) -> usize {
+        let node = if v.len() == 1 {
+            (v[0].clone(), [0; 2])
+        } else {
+            let mid = v.len() / 2;
+            let left = self.build(&v[..mid]);
+            let right = self.build(&v[mid..]);
+            (
+                M::operate(&self.nodes[left].0, &self.nodes[right].0),
+                [left, right],
+            )
+        };
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Returns the length of the array.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of versions.
+    pub fn versions(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Sets `value` at `index` on `version`, and returns the new version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` or `index` is out of bounds.
+    pub fn set(&mut self, version: usize, index: usize, value: M::Set) -> usize {
+        assert!(index < self.n);
+        let root = self.set_inner(self.roots[version], 0, self.n, index, value);
+        self.roots.push(root);
+        self.roots.len() - 1
+    }
+
+    fn set_inner(&mut self, node: usize, l: usize, r: usize, index: usize, value: M::Set) -> usize {
+        let node = if r - l == 1 {
+            (value, [0; 2])
+        } else {
+            let mid = l + (r - l) / 2;
+            let [mut left, mut right] = self.nodes[node].1;
+            if index < mid {
+                left = self.set_inner(left, l, mid, index, value);
+            } else {
+                right = self.set_inner(right, mid, r, index, value);
+            }
+            (
+                M::operate(&self.nodes[left].0, &self.nodes[right].0),
+                [left, right],
+            )
+        };
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Returns the value at `index` on `version`.
+    pub fn get(&self, version: usize, index: usize) -> M::Set {
+        self.query(version, index..=index)
+    }
+
+    /// Returns the fold of `range` on `version`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` or `range` is out of bounds.
+    pub fn query<R: RangeBounds<usize>>(&self, version: usize, range: R) -> M::Set {
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.n,
+        };
+        assert!(l <= r);
+        assert!(r <= self.n);
+
+        self.query_inner(self.roots[version], 0, self.n, l, r)
+    }
+
+    fn query_inner(&self, node: usize, l: usize, r: usize, ql: usize, qr: usize) -> M::Set {
+        if qr <= l || r <= ql {
+            M::id()
+        } else if ql <= l && r <= qr {
+            self.nodes[node].0.clone()
+        } else {
+            let mid = l + (r - l) / 2;
+            let [left, right] = self.nodes[node].1;
+            M::operate(
+                &self.query_inner(left, l, mid, ql, qr),
+                &self.query_inner(right, mid, r, ql, qr),
+            )
+        }
+    }
+}
+
+impl<M: Monoid> From<Vec<M::Set>> for PersistentSegTree<M> {
+    fn from(v: Vec<M::Set>) -> Self {
+        let mut ret = Self::new(v.len());
+        if !v.is_empty() {
+            ret.roots[0] = ret.build(&v);
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PersistentSegTree;
+    use crate::math::num::alge_struct::types::{AddMonoid, MinMonoid};
+    use crate::math::num::{Monoid, Semigroup};
+
+    #[test]
+    fn versions() {
+        let v = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 6];
+        let n = v.len();
+        let mut segtree = PersistentSegTree::<MinMonoid<i32>>::from(v.clone());
+        let mut answers = vec![v];
+        let mut version = 0;
+        for i in 0..30 {
+            // branch from an older version sometimes
+            let base = if i % 4 == 3 { version / 2 } else { version };
+            let mut ans = answers[base].clone();
+            let (k, x) = (i * 7 % n, (i * 5 % 11) as i32);
+            ans[k] = x;
+            version = segtree.set(base, k, x);
+            answers.push(ans);
+            assert_eq!(segtree.versions(), answers.len());
+
+            for (ver, ans) in answers.iter().enumerate() {
+                for l in 0..=n {
+                    for r in l..=n {
+                        let fold = ans[l..r]
+                            .iter()
+                            .fold(MinMonoid::id(), |a, b| MinMonoid::operate(&a, b));
+                        assert_eq!(segtree.query(ver, l..r), fold);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn kth_smallest() {
+        let v = vec![
+            31, 41, 59, 26, 53, 58, 97, 93, 23, 84, 62, 64, 33, 83, 27, 95,
+        ];
+        let n = v.len();
+        let mut sorted = v.clone();
+        sorted.sort();
+
+        // version `i` counts the values of `v[..i]`
+        let mut segtree = PersistentSegTree::<AddMonoid<usize>>::new(n);
+        for (i, x) in v.iter().enumerate() {
+            let k = sorted.binary_search(x).unwrap();
+            let count = segtree.get(i, k);
+            assert_eq!(segtree.set(i, k, count + 1), i + 1);
+        }
+        assert!(segtree.nodes.len() <= 1 + n * 6);
+
+        for l in 0..n {
+            for r in l + 1..=n {
+                let mut ans = v[l..r].to_vec();
+                ans.sort();
+                for (k, ans) in ans.into_iter().enumerate() {
+                    let count = |m: usize| segtree.query(r, ..=m) - segtree.query(l, ..=m);
+                    let m = (0..n).find(|&m| count(m) > k).unwrap();
+                    assert_eq!(sorted[m], ans);
+                }
+            }
+        }
+    }
+}
@@ -87,6 +87,106 @@ impl<M: Monoid> SegmentTree<M> {
         }
         M::operate(&vl, &vr)
     }
+
+    /// Returns the nodes which cover `l..r` in order from left to right.
+    fn nodes(&self, l: usize, r: usize) -> Vec<usize> {
+        let mut l = l + self.n - 1;
+        let mut r = r + self.n - 1;
+        let mut left = vec![];
+        let mut right = vec![];
+        while l < r {
+            if l & 1 == 0 {
+                left.push(l);
+                l += 1;
+            }
+            if r & 1 == 0 {
+                r -= 1;
+                right.push(r);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        left.extend(right.into_iter().rev());
+        left
+    }
+
+    /// Returns the largest `r` such that `pred(&self.query(l..r))` is `true`.
+    ///
+    /// `pred` must be monotone, i.e. once it returns `false` for some `r`,
+    /// it must return `false` for all larger `r`.
+    ///
+    /// This operation is O(log(*n*)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `l > n` or `pred(&M::id())` is `false`.
+    pub fn max_right<F>(&self, l: usize, mut pred: F) -> usize
+    where
+        F: FnMut(&M::Set) -> bool,
+    {
+        assert!(l <= self.n);
+        assert!(pred(&M::id()));
+
+        let mut acc = M::id();
+        for mut i in self.nodes(l, self.n) {
+            let next = M::operate(&acc, &self.tree[i]);
+            if pred(&next) {
+                acc = next;
+                continue;
+            }
+
+            // descend into the node which contains the boundary
+            while i < self.n - 1 {
+                i = i * 2 + 1;
+                let next = M::operate(&acc, &self.tree[i]);
+                if pred(&next) {
+                    acc = next;
+                    i += 1;
+                }
+            }
+            return i - (self.n - 1);
+        }
+        self.n
+    }
+
+    /// Returns the smallest `l` such that `pred(&self.query(l..r))` is `true`.
+    ///
+    /// `pred` must be monotone, i.e. once it returns `false` for some `l`,
+    /// it must return `false` for all smaller `l`.
+    ///
+    /// This operation is O(log(*n*)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r > n` or `pred(&M::id())` is `false`.
+    pub fn min_left<F>(&self, r: usize, mut pred: F) -> usize
+    where
+        F: FnMut(&M::Set) -> bool,
+    {
+        assert!(r <= self.n);
+        assert!(pred(&M::id()));
+
+        let mut acc = M::id();
+        for mut i in self.nodes(0, r).into_iter().rev() {
+            let next = M::operate(&self.tree[i], &acc);
+            if pred(&next) {
+                acc = next;
+                continue;
+            }
+
+            // descend into the node which contains the boundary
+            while i < self.n - 1 {
+                i = i * 2 + 2;
+                let next = M::operate(&self.tree[i], &acc);
+                if pred(&next) {
+                    acc = next;
+                    i -= 1;
+                }
+            }
+            return i + 1 - (self.n - 1);
+        }
+        0
+    }
 }
 
 impl<M: Monoid> From<Vec<M::Set>> for SegmentTree<M> {
@@ -111,7 +211,7 @@ where
 mod tests {
     use super::SegmentTree;
     use crate::math::num::alge_struct::types::monoid::*;
-    use crate::math::num::alge_struct::Monoid;
+    use crate::math::num::alge_struct::{Monoid, Semigroup};
     use core::{cmp::PartialEq, fmt::Debug};
 
     macro_rules! test_segtree {
@@ -139,6 +239,93 @@ mod tests {
             )*};
         }
 
+    #[test]
+    fn max_right() {
+        type Seg = SegmentTree<AddMonoid<usize>>;
+        for n in 1..=12 {
+            let v = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 6][..n].to_vec();
+            let segtree = Seg::from(v.clone());
+            for l in 0..=n {
+                for lim in 0..=50 {
+                    let ans = (l..=n)
+                        .rev()
+                        .find(|&r| v[l..r].iter().sum::<usize>() <= lim)
+                        .unwrap();
+                    assert_eq!(
+                        segtree.max_right(l, |&s| s <= lim),
+                        ans,
+                        "n: {}, l: {}, lim: {}",
+                        n,
+                        l,
+                        lim
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn min_left() {
+        type Seg = SegmentTree<AddMonoid<usize>>;
+        for n in 1..=12 {
+            let v = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 6][..n].to_vec();
+            let segtree = Seg::from(v.clone());
+            for r in 0..=n {
+                for lim in 0..=50 {
+                    let ans = (0..=r)
+                        .find(|&l| v[l..r].iter().sum::<usize>() <= lim)
+                        .unwrap();
+                    assert_eq!(
+                        segtree.min_left(r, |&s| s <= lim),
+                        ans,
+                        "n: {}, r: {}, lim: {}",
+                        n,
+                        r,
+                        lim
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn non_commutative_bisect() {
+        // Checks whether the range is sorted: `(is_sorted, Some((first, last)))`
+        struct Sorted;
+        impl Semigroup for Sorted {
+            type Set = (bool, Option<(i32, i32)>);
+            fn operate(lhs: &Self::Set, rhs: &Self::Set) -> Self::Set {
+                match (lhs.1, rhs.1) {
+                    (Some((lf, ll)), Some((rf, rl))) => {
+                        (lhs.0 && rhs.0 && ll <= rf, Some((lf, rl)))
+                    }
+                    (None, _) => *rhs,
+                    (_, None) => *lhs,
+                }
+            }
+        }
+        impl Monoid for Sorted {
+            fn id() -> Self::Set {
+                (true, None)
+            }
+        }
+
+        let v = vec![2, 7, 1, 8, 2, 8, 8, 1, 8, 2, 8, 4, 6];
+        let n = v.len();
+        let segtree = SegmentTree::<Sorted>::from(
+            v.iter().map(|&x| (true, Some((x, x)))).collect::<Vec<_>>(),
+        );
+        let is_sorted = |s: &[i32]| s.windows(2).all(|w| w[0] <= w[1]);
+        for l in 0..=n {
+            let ans = (l..=n).rev().find(|&r| is_sorted(&v[l..r])).unwrap();
+            assert_eq!(segtree.max_right(l, |s| s.0), ans, "l: {}", l);
+        }
+        for r in 0..=n {
+            let ans = (0..=r).find(|&l| is_sorted(&v[l..r])).unwrap();
+            assert_eq!(segtree.min_left(r, |s| s.0), ans, "r: {}", r);
+        }
+    }
+
     test_segtree! {
         add, AddMonoid
         mul, MulMonoid
@@ -0,0 +1,438 @@
+use super::{Monoid, SegmentTree};
+use core::{
+    cmp::Ordering::{Greater, Less},
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        RangeBounds,
+    },
+};
+
+fn to_range<R: RangeBounds<usize>>(range: &R, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l + 1,
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r + 1,
+        Excluded(&r) => r,
+        Unbounded => n,
+    };
+    assert!(l <= r);
+    assert!(r <= n);
+    (l, r)
+}
+
+/// Calls `f` with each node covering `l..r` in a tree with `n` leaves.
+fn for_each_node<F: FnMut(usize)>(n: usize, l: usize, r: usize, mut f: F) {
+    let mut l = l + n - 1;
+    let mut r = r + n - 1;
+    while l < r {
+        if l & 1 == 0 {
+            f(l);
+        }
+        if r & 1 == 0 {
+            r -= 1;
+            f(r);
+        }
+        l >>= 1;
+        r >>= 1;
+    }
+}
+
+/// A two-dimensional segment tree.
+///
+/// `M` must be commutative since the order of folding is not specified.
+///
+/// # Time complexity
+///
+/// | Algorithm | Worst case               |
+/// | --------- | ------------------------ |
+/// | Memory    | O(*hw*)                  |
+/// | [`set`]   | O(log(*h*) log(*w*))     |
+/// | [`query`] | O(log(*h*) log(*w*))     |
+///
+/// [`set`]: SegmentTree2D::set
+/// [`query`]: SegmentTree2D::query
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::segment_tree::SegmentTree2D;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let mut segtree = SegmentTree2D::<AddMonoid<i32>>::from(vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ]);
+/// assert_eq!(segtree.query(.., 1..), 16);
+/// segtree.set(1, 2, 10);
+/// assert_eq!(segtree.query(1.., ..), 19);
+/// ```
+pub struct SegmentTree2D<M: Monoid> {
+    h: usize,
+    w: usize,
+    tree: Vec<M::Set>,
+}
+
+impl<M: Monoid> SegmentTree2D<M> {
+    /// Creates a `h` x `w` tree filled with `M::id()`.
+    ///
+    /// If `h` or `w` is `0`, the tree is empty and every query returns `M::id()`.
+    pub fn new(h: usize, w: usize) -> Self {
+        let size = (h * 2).saturating_sub(1) * (w * 2).saturating_sub(1);
+        Self {
+            h,
+            w,
+            tree: vec![M::id(); size],
+        }
+    }
+
+    fn idx(&self, i: usize, j: usize) -> usize {
+        i * (self.w * 2 - 1) + j
+    }
+
+    fn update(&mut self, i: usize, j: usize) {
+        let value = if i < self.h - 1 {
+            M::operate(
+                &self.tree[self.idx(i * 2 + 1, j)],
+                &self.tree[self.idx(i * 2 + 2, j)],
+            )
+        } else {
+            M::operate(
+                &self.tree[self.idx(i, j * 2 + 1)],
+                &self.tree[self.idx(i, j * 2 + 2)],
+            )
+        };
+        let idx = self.idx(i, j);
+        self.tree[idx] = value;
+    }
+
+    /// Returns the number of rows and columns.
+    pub fn size(&self) -> (usize, usize) {
+        (self.h, self.w)
+    }
+
+    /// Sets `value` at (`i`, `j`).
+    pub fn set(&mut self, i: usize, j: usize, value: M::Set) {
+        assert!(i < self.h && j < self.w);
+        let mut i = i + self.h - 1;
+        let j = j + self.w - 1;
+        let idx = self.idx(i, j);
+        self.tree[idx] = value;
+        loop {
+            let mut k = j;
+            if i < self.h - 1 {
+                self.update(i, k);
+            }
+            while k > 0 {
+                k = (k - 1) / 2;
+                self.update(i, k);
+            }
+            if i == 0 {
+                break;
+            }
+            i = (i - 1) / 2;
+        }
+    }
+
+    /// Returns the value at (`i`, `j`).
+    pub fn get(&self, i: usize, j: usize) -> &M::Set {
+        assert!(i < self.h && j < self.w);
+        &self.tree[self.idx(i + self.h - 1, j + self.w - 1)]
+    }
+
+    /// Returns the fold of the rectangle `rows` x `cols`.
+    pub fn query<R, C>(&self, rows: R, cols: C) -> M::Set
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (il, ir) = to_range(&rows, self.h);
+        let (jl, jr) = to_range(&cols, self.w);
+        let mut ret = M::id();
+        if il < ir && jl < jr {
+            for_each_node(self.h, il, ir, |i| {
+                for_each_node(self.w, jl, jr, |j| {
+                    ret = M::operate(&ret, &self.tree[self.idx(i, j)]);
+                })
+            });
+        }
+        ret
+    }
+}
+
+impl<M: Monoid> From<Vec<Vec<M::Set>>> for SegmentTree2D<M> {
+    /// # Panics
+    ///
+    /// Panics if the rows have different lengths.
+    fn from(v: Vec<Vec<M::Set>>) -> Self {
+        let h = v.len();
+        let w = v.first().map_or(0, Vec::len);
+        assert!(
+            v.iter().all(|row| row.len() == w),
+            "the rows must have the same length"
+        );
+        let mut ret = Self::new(h, w);
+        if h == 0 || w == 0 {
+            return ret;
+        }
+        for (i, row) in v.into_iter().enumerate() {
+            let offset = ret.idx(i + h - 1, w - 1);
+            ret.tree[offset..offset + w].clone_from_slice(&row);
+        }
+        for i in (0..h * 2 - 1).rev() {
+            if i < h - 1 {
+                (0..w * 2 - 1).for_each(|j| ret.update(i, j));
+            } else {
+                (0..w - 1).rev().for_each(|j| ret.update(i, j));
+            }
+        }
+        ret
+    }
+}
+
+/// A two-dimensional segment tree on a set of points given offline.
+///
+/// Only the points given on construction can be updated,
+/// and the coordinates can be any `Ord` values.
+/// `M` must be commutative since the order of folding is not specified.
+///
+/// # Time complexity
+///
+/// | Algorithm | Worst case              |
+/// | --------- | ----------------------- |
+/// | Memory    | O(*n* log(*n*))         |
+/// | [`set`]   | O(log(*n*)^2)           |
+/// | [`query`] | O(log(*n*)^2)           |
+///
+/// [`set`]: CompressedSegTree2D::set
+/// [`query`]: CompressedSegTree2D::query
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::segment_tree::CompressedSegTree2D;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let points = vec![(1, 1), (-5, 3), (1_000_000_000, -7)];
+/// let mut segtree = CompressedSegTree2D::<_, AddMonoid<i32>>::new(points);
+/// segtree.set(1, 1, 2);
+/// segtree.set(-5, 3, 3);
+/// segtree.set(1_000_000_000, -7, 5);
+/// assert_eq!(segtree.query(.., ..), 10);
+/// assert_eq!(segtree.query(0.., ..=1), 7);
+/// ```
+pub struct CompressedSegTree2D<K, M: Monoid> {
+    xs: Vec<K>,
+    ys: Vec<Vec<K>>,
+    tree: Vec<SegmentTree<M>>,
+}
+
+fn lower_bound<K: Ord>(v: &[K], x: &K) -> usize {
+    v.binary_search_by(|y| if y < x { Less } else { Greater })
+        .unwrap_err()
+}
+
+fn to_index_range<K: Ord, R: RangeBounds<K>>(v: &[K], range: &R) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Included(l) => lower_bound(v, l),
+        Excluded(l) => v
+            .binary_search_by(|y| if y <= l { Less } else { Greater })
+            .unwrap_err(),
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Included(r) => v
+            .binary_search_by(|y| if y <= r { Less } else { Greater })
+            .unwrap_err(),
+        Excluded(r) => lower_bound(v, r),
+        Unbounded => v.len(),
+    };
+    (l, r.max(l))
+}
+
+impl<K: Ord + Clone, M: Monoid> CompressedSegTree2D<K, M> {
+    /// Creates a tree on `points` filled with `M::id()`.
+    pub fn new<I: IntoIterator<Item = (K, K)>>(points: I) -> Self {
+        let mut points = points.into_iter().collect::<Vec<_>>();
+        points.sort();
+        points.dedup();
+        let mut xs = points.iter().map(|(x, _)| x.clone()).collect::<Vec<_>>();
+        xs.dedup();
+        let n = xs.len();
+        if n == 0 {
+            return Self {
+                xs,
+                ys: vec![],
+                tree: vec![],
+            };
+        }
+
+        let mut ys = vec![vec![]; n * 2 - 1];
+        for (x, y) in points {
+            ys[lower_bound(&xs, &x) + n - 1].push(y);
+        }
+        for i in (0..n - 1).rev() {
+            let mut v = [ys[i * 2 + 1].clone(), ys[i * 2 + 2].clone()].concat();
+            v.sort();
+            v.dedup();
+            ys[i] = v;
+        }
+        let tree = ys.iter().map(|v| SegmentTree::new(v.len())).collect();
+        Self { xs, ys, tree }
+    }
+
+    /// Sets `value` at (`x`, `y`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`x`, `y`) is not given on construction.
+    pub fn set(&mut self, x: K, y: K, value: M::Set) {
+        let n = self.xs.len();
+        let mut i = self
+            .xs
+            .binary_search(&x)
+            .expect("the point is not given on construction")
+            + n
+            - 1;
+        let j = self.ys[i]
+            .binary_search(&y)
+            .expect("the point is not given on construction");
+        self.tree[i].set(j, value);
+        while i > 0 {
+            i = (i - 1) / 2;
+            let j = lower_bound(&self.ys[i], &y);
+            let mut value = M::id();
+            for c in i * 2 + 1..i * 2 + 3 {
+                if let Ok(k) = self.ys[c].binary_search(&y) {
+                    value = M::operate(&value, &self.tree[c][k]);
+                }
+            }
+            self.tree[i].set(j, value);
+        }
+    }
+
+    /// Returns the value at (`x`, `y`), or `M::id()` if it is not given on construction.
+    pub fn get(&self, x: K, y: K) -> M::Set {
+        self.query(x.clone()..=x, y.clone()..=y)
+    }
+
+    /// Returns the fold of the rectangle `xs` x `ys`.
+    pub fn query<R, C>(&self, xs: R, ys: C) -> M::Set
+    where
+        R: RangeBounds<K>,
+        C: RangeBounds<K>,
+    {
+        let (l, r) = to_index_range(&self.xs, &xs);
+        let mut ret = M::id();
+        if l < r {
+            for_each_node(self.xs.len(), l, r, |i| {
+                let (l, r) = to_index_range(&self.ys[i], &ys);
+                ret = M::operate(&ret, &self.tree[i].query(l..r));
+            });
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompressedSegTree2D, SegmentTree2D};
+    use crate::math::num::alge_struct::types::{AddMonoid, MaxMonoid};
+
+    #[test]
+    fn segtree2d() {
+        let (h, w) = (5, 7);
+        let mut v = (0..h)
+            .map(|i| {
+                (0..w)
+                    .map(|j| ((i * 31 + j * 17) % 23) as i64)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut segtree = SegmentTree2D::<AddMonoid<i64>>::from(v.clone());
+        let mut maxtree = SegmentTree2D::<MaxMonoid<i64>>::new(h, w);
+        for (i, row) in v.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                maxtree.set(i, j, x);
+            }
+        }
+        for t in 0..20 {
+            let (i, j, x) = (t * 3 % h, t * 5 % w, t as i64 * 7 % 13);
+            v[i][j] = x;
+            segtree.set(i, j, x);
+            maxtree.set(i, j, x);
+            assert_eq!(*segtree.get(i, j), x);
+            for il in 0..=h {
+                for ir in il..=h {
+                    for jl in 0..=w {
+                        for jr in jl..=w {
+                            let cells = v[il..ir].iter().flat_map(|row| &row[jl..jr]);
+                            assert_eq!(segtree.query(il..ir, jl..jr), cells.clone().sum::<i64>());
+                            assert_eq!(
+                                maxtree.query(il..ir, jl..jr),
+                                cells.copied().max().unwrap_or(core::i64::MIN)
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        for &(h, w) in &[(0, 0), (0, 3), (4, 0)] {
+            let segtree = SegmentTree2D::<AddMonoid<i64>>::new(h, w);
+            assert_eq!(segtree.size(), (h, w));
+            assert_eq!(segtree.query(.., ..), 0);
+        }
+        let segtree = SegmentTree2D::<AddMonoid<i64>>::from(vec![]);
+        assert_eq!(segtree.size(), (0, 0));
+        assert_eq!(segtree.query(.., ..), 0);
+        let segtree = SegmentTree2D::<AddMonoid<i64>>::from(vec![vec![]; 3]);
+        assert_eq!(segtree.size(), (3, 0));
+        assert_eq!(segtree.query(1.., ..), 0);
+    }
+
+    #[test]
+    fn compressed() {
+        let points = (0..40)
+            .map(|i| ((i * 37 % 11) as i64 - 5, (i * 13 % 17) as i64 * 3))
+            .collect::<Vec<_>>();
+        let mut values = vec![0; points.len()];
+        let mut segtree = CompressedSegTree2D::<_, AddMonoid<i64>>::new(points.clone());
+        for t in 0..60 {
+            let k = t * 7 % points.len();
+            let (x, y) = points[k];
+            let value = t as i64 % 9 + 1;
+            // duplicated points share the value
+            for (p, v) in points.iter().zip(values.iter_mut()) {
+                if *p == (x, y) {
+                    *v = value;
+                }
+            }
+            segtree.set(x, y, value);
+            assert_eq!(segtree.get(x, y), value);
+            assert_eq!(segtree.get(x, y + 1), 0);
+
+            for xl in -6..=6 {
+                for xr in xl..=6 {
+                    for yl in -1..=49 {
+                        let yr = yl + t as i64 % 20;
+                        let mut seen = vec![];
+                        let mut sum = 0;
+                        for (&(x, y), &v) in points.iter().zip(values.iter()) {
+                            if xl <= x && x < xr && yl <= y && y <= yr && !seen.contains(&(x, y)) {
+                                seen.push((x, y));
+                                sum += v;
+                            }
+                        }
+                        assert_eq!(segtree.query(xl..xr, yl..=yr), sum);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,16 @@
-use super::SegmentTree;
+//! Aliases of the segment trees and the actions of the lazy segment trees
+//! for the common monoids.
+//!
+//! The range add [`LMAdd`] has no presets for [`MulMonoid`] and the bit monoids,
+//! since adding to each element in a range cannot be applied to a product, XOR, OR or AND
+//! of the range in O(1) or O(log *n*) time.
+
+use super::{LSTMonoid, SegmentTree};
 use crate::math::num::alge_struct::types::{
     AddMonoid, BitAndMonoid, BitOrMonoid, BitXorMonoid, MaxMonoid, MinMonoid, MulMonoid,
 };
+use crate::math::num::{monoid_pow, BoundedAbove, BoundedBelow, Monoid, Zero};
+use core::ops::Add;
 
 #[codesnip::entry("AddSegTree", include("SegmentTree", "AddMonoid"))]
 pub type AddSegTree<T> = SegmentTree<AddMonoid<T>>;
@@ -18,160 +27,237 @@ pub type BitOrSegTree<T> = SegmentTree<BitOrMonoid<T>>;
 #[codesnip::entry("BitAndSegTree", include("SegmentTree", "BitAndMonoid"))]
 pub type BitAndSegTree<T> = SegmentTree<BitAndMonoid<T>>;
 
-// #[codesnip::entry]
-// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-// pub struct LMRep<M>(core::marker::PhantomData<M>);
-
-// #[codesnip::entry("LMRep", include("Monoid", "LSTMonoid"))]
-// impl<M: Monoid> LSTMonoid<M> for LMRep<M> {
-//     fn act(set: &M::Set, acter: &Option<M::Set>, range: usize) -> M::Set {
-//         match acter {
-//             Some(acter) => (0..range).fold(M::id(), |acc, _| M::operate(&acc, acter)),
-//             None => set.clone(),
-//         }
-//     }
-
-//     fn merge_act(lhs: &Option<M::Set>, rhs: &Option<M::Set>) -> Option<M::Set> {
-//         match rhs {
-//             Some(_) => rhs.clone(),
-//             None => lhs.clone(),
-//         }
-//     }
-// }
-
-// #[codesnip::entry(include("LMRep", "AddMonoid"))]
-// pub type RepSum<T> = LMRep<AddMonoid<T>>;
-// #[codesnip::entry(include("LMRep", "MulMonoid"))]
-// pub type RepProd<T> = LMRep<MulMonoid<T>>;
-// #[codesnip::entry(include("LMRep", "MaxMonoid"))]
-// pub type RepMax<T> = LMRep<MaxMonoid<T>>;
-// #[codesnip::entry(include("LMRep", "MinMonoid"))]
-// pub type RepMin<T> = LMRep<MinMonoid<T>>;
-// #[codesnip::entry(include("LMRep", "BitXorMonoid"))]
-// pub type RepXor<T> = LMRep<BitXorMonoid<T>>;
-// #[codesnip::entry(include("LMRep", "BitOrMonoid"))]
-// pub type RepOr<T> = LMRep<BitOrMonoid<T>>;
-
-// #[codesnip::entry]
-// pub struct LMAdd<M>(core::marker::PhantomData<M>);
-
-// #[codesnip::entry("LMAdd", include("Monoid", "LSTMonoid"))]
-// impl<M> LSTMonoid<M> for LMAdd<M>
-// where
-//     M: Monoid,
-//     M::Set: core::ops::Add<Output = M::Set>,
-// {
-//     fn act(set: &M::Set, acter: &Option<M::Set>, range: usize) -> M::Set {
-//         match acter {
-//             Some(acter) => (0..range).fold(set.clone(), |acc, _| M::operate(&acc, acter)),
-//             None => set.clone(),
-//         }
-//     }
-
-//     fn merge_act(lhs: &Option<M::Set>, rhs: &Option<M::Set>) -> Option<M::Set> {
-//         match (lhs, rhs) {
-//             (Some(lhs), Some(rhs)) => Some(lhs.clone() + rhs.clone()),
-//             (lhs, rhs) => lhs.clone().or(rhs.clone()),
-//         }
-//     }
-// }
-
-// #[codesnip::entry(include("LMAdd", "AddMonoid"))]
-// pub type AddSum<T> = LMAdd<AddMonoid<T>>;
-// #[codesnip::entry(include("LMAdd", "MulMonoid"))]
-// pub type AddProd<T> = LMAdd<MulMonoid<T>>;
-// #[codesnip::entry(include("LMAdd", "MaxMonoid"))]
-// pub type AddMax<T> = LMAdd<MaxMonoid<T>>;
-// #[codesnip::entry(include("LMAdd", "MinMonoid"))]
-// pub type AddMin<T> = LMAdd<MinMonoid<T>>;
-// #[codesnip::entry(include("LMAdd", "BitXorMonoid"))]
-// pub type AddXor<T> = LMAdd<BitXorMonoid<T>>;
-// #[codesnip::entry(include("LMAdd", "BitOrMonoid"))]
-// pub type AddOr<T> = LMAdd<BitOrMonoid<T>>;
-
-// #[cfg(test)]
-// mod lst_tests {
-//     use super::super::LazySegTree;
-//     use super::*;
-//     use core::fmt::Debug;
-
-//     fn check_segtree<M, LM>(ans: &[M::Set], segtree: &mut LazySegTree<M, LM>)
-//     where
-//         M: Monoid,
-//         M::Set: PartialEq + Debug,
-//         LM: LSTMonoid<M>,
-//     {
-//         let n = ans.len();
-//         // get for each
-//         for i in 0..n {
-//             assert_eq!(
-//                 segtree.get(i).as_ref(),
-//                 Some(&ans[i]),
-//                 "i={}\n{:?}",
-//                 i,
-//                 segtree
-//             );
-//         }
-
-//         // get for each range
-//         for i in 0..n {
-//             for k in i..=n {
-//                 let ans = ans[i..k].iter().fold(M::id(), |a, b| M::operate(&a, b));
-//                 let r = segtree.get(i..k);
-//                 assert_eq!(r.unwrap(), ans, "range: `{}..{}`\n{:?}", i, k, segtree);
-//             }
-//         }
-//     }
-
-//     macro_rules! test_segtree {
-//         ($($name:ident: $lst_monoid:ident, $monoid:ident);* $(;)?) => {$(
-//             mod $name {
-//                 use super::*;
-//                 type Mono = $monoid<isize>;
-//                 type LSTMono = $lst_monoid<Mono>;
-//                 type Seg = LazySegTree<Mono, LSTMono>;
-
-//                 #[test]
-//                 fn from() {
-//                     let v = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 6];
-//                     let mut seg = Seg::from(v.clone());
-//                     check_segtree(&v, &mut seg);
-//                 }
-
-//                 #[test]
-//                 fn operate() {
-//                     let len = 14;
-//                     let mut ans = vec![Mono::id(); len];
-//                     let mut segtree = Seg::new(len);
-//                     for i in 0..len {
-//                         for k in i..=len {
-//                             let val = Some(((k - i) % 7) as isize);
-//                             for m in i..k {
-//                                 ans[m] = LSTMono::act(&ans[m], &val, 1);
-//                             }
-//                             segtree.operate(i..k, val);
-//                             check_segtree(&ans, &mut segtree);
-//                         }
-//                     }
-//                 }
-//             }
-//         )*};
-//     }
-
-//     test_segtree! {
-//         replace_sum:  LMRep, AddMonoid;
-//         replace_prod: LMRep, MulMonoid;
-//         replace_max:  LMRep, MaxMonoid;
-//         replace_min:  LMRep, MinMonoid;
-//         replace_xor:  LMRep, BitXorMonoid;
-//         replace_or:   LMRep, BitOrMonoid;
-//         replace_and:  LMRep, BitAndMonoid;
-//         add_sum:  LMAdd, AddMonoid;
-//         add_prod: LMAdd, MulMonoid;
-//         add_max:  LMAdd, MaxMonoid;
-//         add_min:  LMAdd, MinMonoid;
-//         add_xor:  LMAdd, BitXorMonoid;
-//         add_or:   LMAdd, BitOrMonoid;
-//         add_and:  LMAdd, BitAndMonoid;
-//     }
-// }
+/// Range assign.
+///
+/// Replaces each element in the range with the acter.
+#[codesnip::entry("LMRep")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LMRep<M>(core::marker::PhantomData<M>);
+
+#[codesnip::entry("LMRep", include("Monoid", "LSTMonoid", "monoid_pow"))]
+impl<M: Monoid> LSTMonoid<M> for LMRep<M> {
+    fn act(set: &M::Set, acter: &Option<M::Set>, range: usize) -> M::Set {
+        match acter {
+            Some(acter) => monoid_pow::<M>(acter, range),
+            None => set.clone(),
+        }
+    }
+
+    fn merge_act(lhs: &Option<M::Set>, rhs: &Option<M::Set>) -> Option<M::Set> {
+        match rhs {
+            Some(_) => rhs.clone(),
+            None => lhs.clone(),
+        }
+    }
+}
+
+#[codesnip::entry(include("LMRep", "AddMonoid"))]
+pub type RepSum<T> = LMRep<AddMonoid<T>>;
+#[codesnip::entry(include("LMRep", "MulMonoid"))]
+pub type RepProd<T> = LMRep<MulMonoid<T>>;
+#[codesnip::entry(include("LMRep", "MaxMonoid"))]
+pub type RepMax<T> = LMRep<MaxMonoid<T>>;
+#[codesnip::entry(include("LMRep", "MinMonoid"))]
+pub type RepMin<T> = LMRep<MinMonoid<T>>;
+#[codesnip::entry(include("LMRep", "BitXorMonoid"))]
+pub type RepXor<T> = LMRep<BitXorMonoid<T>>;
+#[codesnip::entry(include("LMRep", "BitOrMonoid"))]
+pub type RepOr<T> = LMRep<BitOrMonoid<T>>;
+#[codesnip::entry(include("LMRep", "BitAndMonoid"))]
+pub type RepAnd<T> = LMRep<BitAndMonoid<T>>;
+
+/// Range add.
+///
+/// Adds the acter to each element in the range.
+/// It is implemented for [`AddMonoid`], [`MinMonoid`] and [`MaxMonoid`],
+/// since the addition does not distribute over the other monoids.
+#[codesnip::entry("LMAdd")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LMAdd<M>(core::marker::PhantomData<M>);
+
+#[codesnip::entry("LMAdd")]
+fn merge_add<T: Clone + Add<Output = T>>(lhs: &Option<T>, rhs: &Option<T>) -> Option<T> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs.clone() + rhs.clone()),
+        (lhs, rhs) => lhs.clone().or_else(|| rhs.clone()),
+    }
+}
+
+/// Each element `x` becomes `x + acter`, so the sum grows by `acter * range`.
+#[codesnip::entry("AddSum", include("LMAdd", "AddMonoid", "LSTMonoid", "monoid_pow"))]
+impl<T: Clone + Zero + Add<Output = T>> LSTMonoid<AddMonoid<T>> for LMAdd<AddMonoid<T>> {
+    fn act(set: &T, acter: &Option<T>, range: usize) -> T {
+        match acter {
+            Some(acter) => set.clone() + monoid_pow::<AddMonoid<T>>(acter, range),
+            None => set.clone(),
+        }
+    }
+
+    fn merge_act(lhs: &Option<T>, rhs: &Option<T>) -> Option<T> {
+        merge_add(lhs, rhs)
+    }
+}
+
+/// Each element `x` becomes `x + acter`, except `T::upper_bound()` which is the identity.
+#[codesnip::entry("AddMin", include("LMAdd", "MinMonoid", "LSTMonoid"))]
+impl<T> LSTMonoid<MinMonoid<T>> for LMAdd<MinMonoid<T>>
+where
+    T: Clone + Ord + BoundedAbove + Add<Output = T>,
+{
+    fn act(set: &T, acter: &Option<T>, _range: usize) -> T {
+        match acter {
+            Some(acter) if *set != T::upper_bound() => set.clone() + acter.clone(),
+            _ => set.clone(),
+        }
+    }
+
+    fn merge_act(lhs: &Option<T>, rhs: &Option<T>) -> Option<T> {
+        merge_add(lhs, rhs)
+    }
+}
+
+/// Each element `x` becomes `x + acter`, except `T::lower_bound()` which is the identity.
+#[codesnip::entry("AddMax", include("LMAdd", "MaxMonoid", "LSTMonoid"))]
+impl<T> LSTMonoid<MaxMonoid<T>> for LMAdd<MaxMonoid<T>>
+where
+    T: Clone + Ord + BoundedBelow + Add<Output = T>,
+{
+    fn act(set: &T, acter: &Option<T>, _range: usize) -> T {
+        match acter {
+            Some(acter) if *set != T::lower_bound() => set.clone() + acter.clone(),
+            _ => set.clone(),
+        }
+    }
+
+    fn merge_act(lhs: &Option<T>, rhs: &Option<T>) -> Option<T> {
+        merge_add(lhs, rhs)
+    }
+}
+
+#[codesnip::entry(include("LMAdd", "AddMonoid"))]
+pub type AddSum<T> = LMAdd<AddMonoid<T>>;
+/// Range add and range max.
+///
+/// An element equal to `T::lower_bound()` is the identity and never changes by adding,
+/// so a value used as -INF must be above `T::lower_bound()`, e.g. `i64::MIN / 2`.
+#[codesnip::entry(include("LMAdd", "MaxMonoid"))]
+pub type AddMax<T> = LMAdd<MaxMonoid<T>>;
+/// Range add and range min.
+///
+/// An element equal to `T::upper_bound()` is the identity and never changes by adding,
+/// so a value used as INF must be below `T::upper_bound()`, e.g. `i64::MAX / 2`.
+#[codesnip::entry(include("LMAdd", "MinMonoid"))]
+pub type AddMin<T> = LMAdd<MinMonoid<T>>;
+
+#[cfg(test)]
+mod lst_tests {
+    use super::super::LazySegTree;
+    use super::*;
+    use core::fmt::Debug;
+
+    fn check_segtree<M, LM>(ans: &[M::Set], segtree: &mut LazySegTree<M, LM>)
+    where
+        M: Monoid,
+        M::Set: PartialEq + Debug,
+        LM: LSTMonoid<M>,
+    {
+        let n = ans.len();
+        // get for each range first, so that pending actions are not evaluated in advance
+        for i in 0..n {
+            for k in i..=n {
+                let ans = ans[i..k].iter().fold(M::id(), |a, b| M::operate(&a, b));
+                let r = segtree.get(i..k);
+                assert_eq!(r.unwrap(), ans, "range: `{}..{}`\n{:?}", i, k, segtree);
+            }
+        }
+
+        // get for each
+        for (i, ans) in ans.iter().enumerate() {
+            assert_eq!(segtree.get(i).as_ref(), Some(ans), "i={}\n{:?}", i, segtree);
+        }
+    }
+
+    macro_rules! test_segtree {
+        ($($name:ident: $lst_monoid:ident, $monoid:ident, |$x:ident, $v:ident| $expected:expr);* $(;)?) => {$(
+            mod $name {
+                use super::*;
+                type Mono = $monoid<isize>;
+                type LSTMono = $lst_monoid<Mono>;
+                type Seg = LazySegTree<Mono, LSTMono>;
+
+                #[test]
+                fn from() {
+                    let v = vec![2, 7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 6];
+                    let mut seg = Seg::from(v.clone());
+                    check_segtree(&v, &mut seg);
+                }
+
+                #[test]
+                fn operate() {
+                    let len = 14;
+                    let mut ans = vec![0; len];
+                    let mut segtree = Seg::from(ans.clone());
+                    for i in 0..len {
+                        for k in i..=len {
+                            let $v = ((k - i) % 7) as isize;
+                            for $x in &mut ans[i..k] {
+                                *$x = $expected;
+                            }
+                            segtree.operate(i..k, Some($v));
+                            check_segtree(&ans, &mut segtree);
+                        }
+                    }
+                }
+            }
+        )*};
+    }
+
+    test_segtree! {
+        replace_sum:  LMRep, AddMonoid, |x, v| v;
+        replace_prod: LMRep, MulMonoid, |x, v| v;
+        replace_max:  LMRep, MaxMonoid, |x, v| v;
+        replace_min:  LMRep, MinMonoid, |x, v| v;
+        replace_xor:  LMRep, BitXorMonoid, |x, v| v;
+        replace_or:   LMRep, BitOrMonoid, |x, v| v;
+        replace_and:  LMRep, BitAndMonoid, |x, v| v;
+        add_sum: LMAdd, AddMonoid, |x, v| *x + v;
+        add_max: LMAdd, MaxMonoid, |x, v| *x + v;
+        add_min: LMAdd, MinMonoid, |x, v| *x + v;
+    }
+
+    #[test]
+    fn range_add() {
+        let mut segtree = LazySegTree::<MinMonoid<i64>, AddMin<i64>>::new(5);
+        segtree.operate(.., Some(3));
+        assert_eq!(segtree.get(..), Some(core::i64::MAX));
+        segtree.operate(1..2, Some(-2));
+        assert_eq!(segtree.get(..), Some(core::i64::MAX));
+
+        let mut segtree = LazySegTree::<MaxMonoid<i64>, AddMax<i64>>::from(vec![4, 1, 7]);
+        segtree.operate(..2, Some(5));
+        segtree.operate(1.., Some(-3));
+        assert_eq!(segtree.get(..), Some(9));
+        assert_eq!(segtree.get(1..), Some(4));
+    }
+
+    #[test]
+    fn range_add_to_bound() {
+        // the bounds are the identities, which adding keeps as they are
+        let inf = core::i64::MAX;
+        let mut segtree = LazySegTree::<MinMonoid<i64>, AddMin<i64>>::from(vec![inf, 5, inf]);
+        segtree.operate(.., Some(-10));
+        assert_eq!(segtree.get(0), Some(inf));
+        assert_eq!(segtree.get(..), Some(-5));
+        assert_eq!(segtree.get(2..), Some(inf));
+
+        let inf = core::i64::MAX / 2;
+        let mut segtree = LazySegTree::<MinMonoid<i64>, AddMin<i64>>::from(vec![inf, 5, inf]);
+        segtree.operate(.., Some(-10));
+        assert_eq!(segtree.get(2..), Some(inf - 10));
+
+        let mut segtree = LazySegTree::<MaxMonoid<i64>, AddMax<i64>>::from(vec![core::i64::MIN, 5]);
+        segtree.operate(.., Some(10));
+        assert_eq!(segtree.get(0), Some(core::i64::MIN));
+        assert_eq!(segtree.get(..), Some(15));
+    }
+}
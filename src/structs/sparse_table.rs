@@ -0,0 +1,240 @@
+use crate::math::num::{Idempotent, Semigroup};
+
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+fn to_range<R: RangeBounds<usize>>(range: R, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l + 1,
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r + 1,
+        Excluded(&r) => r,
+        Unbounded => n,
+    };
+    assert!(l < r, "the range must not be empty");
+    assert!(r <= n);
+    (l, r)
+}
+
+/// Returns `floor(log2(x))`.
+fn log2(x: usize) -> usize {
+    (0_usize.leading_zeros() - x.leading_zeros() - 1) as usize
+}
+
+/// A static table answering range folds of an [`Idempotent`] semigroup in O(1).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::sparse_table::SparseTable;
+/// use lib_rust::math::num::alge_struct::types::{GcdMonoid, MinMonoid};
+///
+/// let table = SparseTable::<MinMonoid<i32>>::from(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(table.query(..), 1);
+/// assert_eq!(table.query(4..7), 2);
+///
+/// let table = SparseTable::<GcdMonoid<u32>>::from(vec![12, 18, 30, 7]);
+/// assert_eq!(table.query(..3), 6);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm | Worst case        |
+/// | --------- | ----------------- |
+/// | Build     | O(*n* log(*n*))   |
+/// | [`query`] | O(1)              |
+///
+/// [`query`]: SparseTable::query
+pub struct SparseTable<S: Idempotent> {
+    // `table[k][i]` is the fold of `i..i + 2^k`
+    table: Vec<Vec<S::Set>>,
+}
+
+impl<S: Idempotent> SparseTable<S> {
+    pub fn len(&self) -> usize {
+        self.table.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the fold of `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty or out of bounds.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> S::Set {
+        let (l, r) = to_range(range, self.len());
+        let k = log2(r - l);
+        S::operate(&self.table[k][l], &self.table[k][r - (1 << k)])
+    }
+}
+
+impl<S: Idempotent> From<Vec<S::Set>> for SparseTable<S> {
+    fn from(v: Vec<S::Set>) -> Self {
+        let n = v.len();
+        let mut table = vec![v];
+        let mut k = 1;
+        while 1 << k <= n {
+            let prev = &table[k - 1];
+            let row = (0..=n - (1 << k))
+                .map(|i| S::operate(&prev[i], &prev[i + (1 << (k - 1))]))
+                .collect();
+            table.push(row);
+            k += 1;
+        }
+        Self { table }
+    }
+}
+
+/// A static table answering range folds of any [`Semigroup`] in O(1).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::sparse_table::DisjointSparseTable;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let table = DisjointSparseTable::<AddMonoid<i32>>::from(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(table.query(..), 31);
+/// assert_eq!(table.query(4..7), 16);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm | Worst case        |
+/// | --------- | ----------------- |
+/// | Build     | O(*n* log(*n*))   |
+/// | [`query`] | O(1)              |
+///
+/// [`query`]: DisjointSparseTable::query
+pub struct DisjointSparseTable<S: Semigroup> {
+    // `table[k]` is the folds from/to the middles of the blocks of size `2^(k + 1)`
+    table: Vec<Vec<S::Set>>,
+}
+
+impl<S: Semigroup> DisjointSparseTable<S> {
+    pub fn len(&self) -> usize {
+        self.table.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the fold of `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty or out of bounds.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> S::Set {
+        let (l, r) = to_range(range, self.len());
+        let r = r - 1;
+        if l == r {
+            self.table[0][l].clone()
+        } else {
+            let k = log2(l ^ r);
+            S::operate(&self.table[k][l], &self.table[k][r])
+        }
+    }
+}
+
+impl<S: Semigroup> From<Vec<S::Set>> for DisjointSparseTable<S> {
+    fn from(v: Vec<S::Set>) -> Self {
+        let n = v.len();
+        let mut table = vec![v.clone()];
+        let mut k = 1;
+        while 1 << k < n {
+            let half = 1 << k;
+            let mut row = v.clone();
+            for mid in (half..n).step_by(half * 2) {
+                for i in (mid - half..mid - 1).rev() {
+                    row[i] = S::operate(&v[i], &row[i + 1]);
+                }
+                for i in mid + 1..n.min(mid + half) {
+                    row[i] = S::operate(&row[i - 1], &v[i]);
+                }
+            }
+            table.push(row);
+            k += 1;
+        }
+        Self { table }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisjointSparseTable, SparseTable};
+    use crate::math::num::alge_struct::types::{
+        AddMonoid, BitAndMonoid, BitOrMonoid, GcdMonoid, MaxMonoid, MinMonoid,
+    };
+    use crate::math::num::Semigroup;
+
+    fn brute<S: Semigroup>(v: &[S::Set]) -> S::Set {
+        v[1..]
+            .iter()
+            .fold(v[0].clone(), |acc, x| S::operate(&acc, x))
+    }
+
+    #[test]
+    fn sparse_table() {
+        for n in 1..40 {
+            let v = (0..n).map(|i| (i * 37 + 11) % 29 * 6).collect::<Vec<u64>>();
+            let min = SparseTable::<MinMonoid<u64>>::from(v.clone());
+            let max = SparseTable::<MaxMonoid<u64>>::from(v.clone());
+            let and = SparseTable::<BitAndMonoid<u64>>::from(v.clone());
+            let or = SparseTable::<BitOrMonoid<u64>>::from(v.clone());
+            let gcd = SparseTable::<GcdMonoid<u64>>::from(v.clone());
+            assert_eq!(min.len(), n as usize);
+            for l in 0..v.len() {
+                for r in l + 1..=v.len() {
+                    assert_eq!(min.query(l..r), brute::<MinMonoid<_>>(&v[l..r]));
+                    assert_eq!(max.query(l..r), brute::<MaxMonoid<_>>(&v[l..r]));
+                    assert_eq!(and.query(l..r), brute::<BitAndMonoid<_>>(&v[l..r]));
+                    assert_eq!(or.query(l..r), brute::<BitOrMonoid<_>>(&v[l..r]));
+                    assert_eq!(gcd.query(l..r), brute::<GcdMonoid<_>>(&v[l..r]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn disjoint_sparse_table() {
+        // concatenation checks the order of the operation
+        struct Concat;
+        impl Semigroup for Concat {
+            type Set = String;
+            fn operate(lhs: &String, rhs: &String) -> String {
+                lhs.clone() + rhs
+            }
+        }
+
+        for n in 1..40 {
+            let v = (0..n).map(|i| (i * 37 + 11) % 29).collect::<Vec<i64>>();
+            let s = (0..n)
+                .map(|i| ((b'a' + (i * 7 % 26) as u8) as char).to_string())
+                .collect::<Vec<_>>();
+            let sum = DisjointSparseTable::<AddMonoid<i64>>::from(v.clone());
+            let concat = DisjointSparseTable::<Concat>::from(s.clone());
+            for l in 0..v.len() {
+                for r in l + 1..=v.len() {
+                    assert_eq!(sum.query(l..r), v[l..r].iter().sum::<i64>());
+                    assert_eq!(concat.query(l..=r - 1), s[l..r].concat());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_range() {
+        let table = SparseTable::<MinMonoid<i32>>::from(vec![1, 2, 3]);
+        table.query(1..1);
+    }
+}
@@ -0,0 +1,425 @@
+use crate::math::num::Monoid;
+use crate::structs::segment_tree::{LSTAction, LSTMonoid};
+
+use core::mem;
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+/// The action which changes nothing, used for [`ImplicitTreap`] without actions.
+pub struct NoAction;
+
+impl<M: Monoid> LSTMonoid<M> for NoAction {
+    fn act(set: &M::Set, _acter: &Option<M::Set>, _range: usize) -> M::Set {
+        set.clone()
+    }
+
+    fn merge_act(_lhs: &Option<M::Set>, _rhs: &Option<M::Set>) -> Option<M::Set> {
+        None
+    }
+}
+
+struct Node<M: Monoid, A: LSTAction<M>> {
+    value: M::Set,
+    fold: M::Set,
+    // the fold from the back to the front
+    rev_fold: M::Set,
+    len: usize,
+    priority: u64,
+    // whether the children must be reversed
+    rev: bool,
+    lazy: A::Act,
+    children: [Option<Box<Node<M, A>>>; 2],
+}
+
+type Tree<M, A> = Option<Box<Node<M, A>>>;
+
+fn len<M: Monoid, A: LSTAction<M>>(tree: &Tree<M, A>) -> usize {
+    tree.as_ref().map_or(0, |node| node.len)
+}
+
+impl<M: Monoid, A: LSTAction<M>> Node<M, A> {
+    fn new(value: M::Set, priority: u64) -> Self {
+        Self {
+            fold: value.clone(),
+            rev_fold: value.clone(),
+            value,
+            len: 1,
+            priority,
+            rev: false,
+            lazy: A::id_act(),
+            children: [None, None],
+        }
+    }
+
+    fn reverse(&mut self) {
+        self.children.swap(0, 1);
+        mem::swap(&mut self.fold, &mut self.rev_fold);
+        self.rev ^= true;
+    }
+
+    fn apply(&mut self, act: &A::Act) {
+        self.value = A::act(act, &self.value, 1);
+        self.fold = A::act(act, &self.fold, self.len);
+        self.rev_fold = A::act(act, &self.rev_fold, self.len);
+        self.lazy = A::merge_act(&self.lazy, act);
+    }
+
+    fn push(&mut self) {
+        let rev = mem::replace(&mut self.rev, false);
+        let lazy = mem::replace(&mut self.lazy, A::id_act());
+        let is_id_act = A::is_id_act(&lazy);
+        for child in self.children.iter_mut().flatten() {
+            if rev {
+                child.reverse();
+            }
+            if !is_id_act {
+                child.apply(&lazy);
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        let [left, right] = &self.children;
+        self.len = len(left) + 1 + len(right);
+        self.fold = self.value.clone();
+        self.rev_fold = self.value.clone();
+        if let Some(left) = left {
+            self.fold = M::operate(&left.fold, &self.fold);
+            self.rev_fold = M::operate(&self.rev_fold, &left.rev_fold);
+        }
+        if let Some(right) = right {
+            self.fold = M::operate(&self.fold, &right.fold);
+            self.rev_fold = M::operate(&right.rev_fold, &self.rev_fold);
+        }
+    }
+}
+
+/// Splits `tree` into the first `k` elements and the rest.
+fn split<M: Monoid, A: LSTAction<M>>(tree: Tree<M, A>, k: usize) -> (Tree<M, A>, Tree<M, A>) {
+    match tree {
+        None => (None, None),
+        Some(mut node) => {
+            node.push();
+            let left_len = len(&node.children[0]);
+            if k <= left_len {
+                let (left, right) = split(node.children[0].take(), k);
+                node.children[0] = right;
+                node.update();
+                (left, Some(node))
+            } else {
+                let (left, right) = split(node.children[1].take(), k - left_len - 1);
+                node.children[1] = left;
+                node.update();
+                (Some(node), right)
+            }
+        }
+    }
+}
+
+fn merge<M: Monoid, A: LSTAction<M>>(lhs: Tree<M, A>, rhs: Tree<M, A>) -> Tree<M, A> {
+    match (lhs, rhs) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut lhs), Some(mut rhs)) => {
+            if lhs.priority > rhs.priority {
+                lhs.push();
+                lhs.children[1] = merge(lhs.children[1].take(), Some(rhs));
+                lhs.update();
+                Some(lhs)
+            } else {
+                rhs.push();
+                rhs.children[0] = merge(Some(lhs), rhs.children[0].take());
+                rhs.update();
+                Some(rhs)
+            }
+        }
+    }
+}
+
+/// A sequence based on a treap with implicit keys.
+///
+/// The elements are folded by `M` and can be changed by the action `A` as [`LazySegTree`].
+///
+/// [`LazySegTree`]: crate::structs::segment_tree::LazySegTree
+///
+/// # Time complexity
+///
+/// All the operations are expected O(log(*n*)).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::treap::ImplicitTreap;
+/// use lib_rust::structs::segment_tree::types::AddSum;
+/// use lib_rust::math::num::alge_struct::types::AddMonoid;
+///
+/// let mut treap = ImplicitTreap::<AddMonoid<i64>, AddSum<i64>>::from(vec![1, 2, 3, 4, 5]);
+/// treap.reverse(1..4);
+/// assert_eq!(treap.to_vec(), vec![1, 4, 3, 2, 5]);
+/// treap.apply(..2, Some(10));
+/// assert_eq!(treap.fold(..3), 28);
+///
+/// // move `[4, 3]` to the back
+/// let (front, back) = treap.split(1);
+/// let (middle, back) = back.split(2);
+/// let mut treap = front.merge(back).merge(middle);
+/// assert_eq!(treap.to_vec(), vec![11, 2, 5, 14, 3]);
+/// assert_eq!(treap.remove(1), 2);
+/// treap.insert(0, 7);
+/// assert_eq!(treap.to_vec(), vec![7, 11, 5, 14, 3]);
+/// ```
+pub struct ImplicitTreap<M: Monoid, A: LSTAction<M> = NoAction> {
+    root: Tree<M, A>,
+    seed: u64,
+}
+
+impl<M: Monoid, A: LSTAction<M>> ImplicitTreap<M, A> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            seed: 0x9e37_79b9_7f4a_7c15,
+        }
+    }
+
+    fn rand(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    pub fn len(&self) -> usize {
+        len(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Splits the sequence into `..pos` and `pos..`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos > len`.
+    pub fn split(self, pos: usize) -> (Self, Self) {
+        assert!(pos <= self.len());
+        let (left, right) = split(self.root, pos);
+        let seed = self.seed;
+        (
+            Self { root: left, seed },
+            Self {
+                root: right,
+                seed: seed.rotate_left(32),
+            },
+        )
+    }
+
+    /// Concatenates `other` to the back.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            root: merge(self.root, other.root),
+            seed: self.seed,
+        }
+    }
+
+    /// Inserts `value` at `pos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos > len`.
+    pub fn insert(&mut self, pos: usize, value: M::Set) {
+        assert!(pos <= self.len());
+        let node = Some(Box::new(Node::new(value, self.rand())));
+        let (left, right) = split(self.root.take(), pos);
+        self.root = merge(merge(left, node), right);
+    }
+
+    /// Removes and returns the element at `pos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos >= len`.
+    pub fn remove(&mut self, pos: usize) -> M::Set {
+        assert!(pos < self.len());
+        let (left, right) = split(self.root.take(), pos);
+        let (node, right) = split(right, 1);
+        self.root = merge(left, right);
+        node.unwrap().value
+    }
+
+    pub fn push_back(&mut self, value: M::Set) {
+        self.insert(self.len(), value);
+    }
+
+    /// Returns the element at `pos`.
+    pub fn get(&mut self, pos: usize) -> M::Set {
+        self.fold(pos..=pos)
+    }
+
+    fn range<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Included(&l) => l,
+            Excluded(&l) => l + 1,
+            Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Included(&r) => r + 1,
+            Excluded(&r) => r,
+            Unbounded => self.len(),
+        };
+        assert!(l <= r);
+        assert!(r <= self.len());
+        (l, r)
+    }
+
+    /// Calls `f` with the subtree of `range`.
+    fn with_range<R, F, T>(&mut self, range: R, f: F) -> T
+    where
+        R: RangeBounds<usize>,
+        F: FnOnce(&mut Tree<M, A>) -> T,
+    {
+        let (l, r) = self.range(range);
+        let (left, right) = split(self.root.take(), r);
+        let (left, mut middle) = split(left, l);
+        let ret = f(&mut middle);
+        self.root = merge(merge(left, middle), right);
+        ret
+    }
+
+    /// Returns the fold of `range`.
+    pub fn fold<R: RangeBounds<usize>>(&mut self, range: R) -> M::Set {
+        self.with_range(range, |tree| {
+            tree.as_ref().map_or_else(M::id, |node| node.fold.clone())
+        })
+    }
+
+    /// Reverses `range`.
+    pub fn reverse<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.with_range(range, |tree| {
+            if let Some(node) = tree {
+                node.reverse();
+            }
+        })
+    }
+
+    /// Applies `act` to each element in `range`.
+    pub fn apply<R: RangeBounds<usize>, T: Into<A::Act>>(&mut self, range: R, act: T) {
+        let act = act.into();
+        self.with_range(range, |tree| {
+            if let Some(node) = tree {
+                node.apply(&act);
+            }
+        })
+    }
+
+    /// Returns the elements from the front to the back.
+    pub fn to_vec(&mut self) -> Vec<M::Set> {
+        fn dfs<M: Monoid, A: LSTAction<M>>(tree: &mut Tree<M, A>, v: &mut Vec<M::Set>) {
+            if let Some(node) = tree {
+                node.push();
+                dfs(&mut node.children[0], v);
+                v.push(node.value.clone());
+                dfs(&mut node.children[1], v);
+            }
+        }
+        let mut v = Vec::with_capacity(self.len());
+        dfs(&mut self.root, &mut v);
+        v
+    }
+}
+
+impl<M: Monoid, A: LSTAction<M>> Default for ImplicitTreap<M, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Monoid, A: LSTAction<M>> From<Vec<M::Set>> for ImplicitTreap<M, A> {
+    fn from(v: Vec<M::Set>) -> Self {
+        let mut ret = Self::new();
+        v.into_iter().for_each(|x| ret.push_back(x));
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImplicitTreap;
+    use crate::math::num::alge_struct::types::AddMonoid;
+    use crate::math::num::{Monoid, Semigroup};
+    use crate::structs::segment_tree::types::{AddSum, RepSum};
+
+    struct Concat;
+    impl Semigroup for Concat {
+        type Set = String;
+        fn operate(lhs: &String, rhs: &String) -> String {
+            lhs.clone() + rhs
+        }
+    }
+    impl Monoid for Concat {
+        fn id() -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn sequence() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut rand = |m: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % m as u64) as usize
+        };
+
+        let mut treap = ImplicitTreap::<Concat>::new();
+        let mut v: Vec<String> = vec![];
+        for i in 0..2000 {
+            let n = v.len();
+            let l = rand(n + 1);
+            let r = l + rand(n - l + 1);
+            match rand(6) {
+                0 | 1 => {
+                    let s = ((b'a' + (i % 26) as u8) as char).to_string();
+                    treap.insert(l, s.clone());
+                    v.insert(l, s);
+                }
+                2 if l < n => assert_eq!(treap.remove(l), v.remove(l)),
+                3 => {
+                    treap.reverse(l..r);
+                    v[l..r].reverse();
+                }
+                4 => {
+                    let (a, b) = treap.split(l);
+                    let (b, c) = b.split(r - l);
+                    treap = a.merge(c).merge(b);
+                    let middle = v.drain(l..r).collect::<Vec<_>>();
+                    v.extend(middle);
+                }
+                _ => assert_eq!(treap.fold(l..r), v[l..r].concat()),
+            }
+            assert_eq!(treap.len(), v.len());
+        }
+        assert_eq!(treap.to_vec(), v);
+    }
+
+    #[test]
+    fn action() {
+        let mut treap = ImplicitTreap::<AddMonoid<i64>, AddSum<i64>>::from(vec![5, 3, 8, 1, 4]);
+        treap.apply(1..4, Some(10));
+        assert_eq!(treap.to_vec(), vec![5, 13, 18, 11, 4]);
+        treap.reverse(..3);
+        assert_eq!(treap.fold(..2), 31);
+        treap.apply(2.., Some(-10));
+        assert_eq!(treap.to_vec(), vec![18, 13, -5, 1, -6]);
+        assert_eq!(treap.fold(..), 21);
+
+        let mut treap = ImplicitTreap::<AddMonoid<i64>, RepSum<i64>>::from(vec![1; 6]);
+        treap.apply(2..5, Some(3));
+        treap.insert(3, 100);
+        assert_eq!(treap.fold(..), 1 + 1 + 3 + 100 + 3 + 3 + 1);
+        treap.reverse(1..);
+        assert_eq!(treap.to_vec(), vec![1, 1, 3, 3, 100, 3, 1]);
+    }
+}
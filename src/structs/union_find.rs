@@ -1,39 +1,82 @@
-pub struct UnionFind {
+use crate::math::num::Semigroup;
+
+/// A union-find with an aggregate for each group, merged by `S` on [`union`](Self::union).
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::union_find::UnionFind;
+/// use lib_rust::math::num::alge_struct::types::MinMonoid;
+///
+/// let mut uf = UnionFind::new(4);
+/// uf.union(0, 2);
+/// assert_eq!(uf.count(), 3);
+/// assert_eq!(uf.groups(), vec![vec![0, 2], vec![1], vec![3]]);
+///
+/// let mut uf = UnionFind::<MinMonoid<i32>>::with_data(vec![5, 3, 8, 1]);
+/// uf.union(0, 1);
+/// uf.union(1, 2);
+/// assert_eq!(*uf.data(2), 3);
+/// ```
+pub struct UnionFind<S: Semigroup = ()> {
     parent: Vec<usize>,
     size: Vec<usize>,
+    count: usize,
+    // only valid at the roots
+    data: Vec<S::Set>,
 }
 
 impl UnionFind {
     pub fn new(n: usize) -> Self {
+        Self::with_data(vec![(); n])
+    }
+}
+
+impl<S: Semigroup> UnionFind<S> {
+    /// Creates a union-find where the `i`-th element has `data[i]`.
+    pub fn with_data(data: Vec<S::Set>) -> Self {
+        let n = data.len();
         let parent = (0..n).collect::<Vec<_>>();
         let size = vec![1; n];
-        Self { parent, size }
+        Self {
+            parent,
+            size,
+            count: n,
+            data,
+        }
     }
 
     pub fn len(&self) -> usize {
         self.parent.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of groups.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
     pub fn find(&self, x: usize) -> usize {
         assert!(x < self.parent.len());
-        let parent = self.parent[x];
-        if parent != x {
-            self.find(parent)
-        } else {
-            parent
+        let mut x = x;
+        while self.parent[x] != x {
+            x = self.parent[x];
         }
+        x
     }
 
     pub fn find_mut(&mut self, x: usize) -> usize {
-        assert!(x < self.parent.len());
-        let parent = self.parent[x];
-        if parent != x {
-            let root = self.find_mut(parent);
+        let root = self.find(x);
+        let mut x = x;
+        while self.parent[x] != root {
+            let parent = self.parent[x];
             self.parent[x] = root;
-            root
-        } else {
-            parent
+            x = parent;
         }
+        root
     }
 
     pub fn equiv(&self, a: usize, b: usize) -> bool {
@@ -49,6 +92,20 @@ impl UnionFind {
         self.size[root]
     }
 
+    /// Returns the aggregate of the group of `x`.
+    pub fn data(&self, x: usize) -> &S::Set {
+        &self.data[self.find(x)]
+    }
+
+    /// Returns the aggregate of the group of `x`.
+    pub fn data_mut(&mut self, x: usize) -> &mut S::Set {
+        let root = self.find_mut(x);
+        &mut self.data[root]
+    }
+
+    /// Merges the groups of `a` and `b`, and returns whether they were different.
+    ///
+    /// The aggregate becomes `S::operate(data(a), data(b))`.
     pub fn union(&mut self, a: usize, b: usize) -> bool {
         if a == b {
             return false;
@@ -60,17 +117,37 @@ impl UnionFind {
             return false;
         }
 
+        let data = S::operate(&self.data[a], &self.data[b]);
         let asize = self.size[a];
         let bsize = self.size[b];
-        if asize > bsize {
+        let root = if asize > bsize {
             self.parent[b] = a;
             self.size[a] += bsize;
+            a
         } else {
             self.parent[a] = b;
             self.size[b] += asize;
-        }
+            b
+        };
+        self.data[root] = data;
+        self.count -= 1;
         true
     }
+
+    /// Returns the groups in ascending order of their smallest elements.
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![None; self.len()];
+        let mut groups = Vec::with_capacity(self.count);
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let i = *index[root].get_or_insert_with(|| {
+                groups.push(Vec::with_capacity(self.size[root]));
+                groups.len() - 1
+            });
+            groups[i].push(x);
+        }
+        groups
+    }
 }
 
 #[cfg(test)]
@@ -89,4 +166,56 @@ mod tests {
             assert!(uf.equiv(i, (i + 2) % size));
         }
     }
+
+    #[test]
+    fn groups() {
+        let size = 10;
+        let mut uf = UnionFind::new(size);
+        assert_eq!(uf.count(), size);
+        for i in 0..size {
+            uf.union(i, i % 3);
+            uf.union(i % 3, i);
+        }
+        assert_eq!(uf.count(), 3);
+        assert_eq!(
+            uf.groups(),
+            vec![vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]
+        );
+    }
+
+    #[test]
+    fn long_chain() {
+        let size = 1_000_000;
+        let mut uf = UnionFind::new(size);
+        for i in 1..size {
+            // make a chain by attaching the larger group
+            uf.parent[i - 1] = i;
+            uf.size[i] += uf.size[i - 1];
+        }
+        assert_eq!(uf.find(0), size - 1);
+        assert_eq!(uf.find_mut(0), size - 1);
+        assert_eq!(uf.parent[size / 2], size - 1);
+    }
+
+    #[test]
+    fn data() {
+        use crate::math::num::alge_struct::types::{AddMonoid, MaxMonoid};
+
+        let v = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let mut sum = UnionFind::<AddMonoid<i32>>::with_data(v.clone());
+        let mut max = UnionFind::<MaxMonoid<i32>>::with_data(v.clone());
+        for &(a, b) in &[(0, 1), (2, 3), (1, 3), (5, 7), (4, 4)] {
+            sum.union(a, b);
+            max.union(a, b);
+        }
+        for group in sum.groups() {
+            let expected = group.iter().map(|&x| v[x]).sum::<i32>();
+            for &x in &group {
+                assert_eq!(*sum.data(x), expected);
+                assert_eq!(*max.data(x), group.iter().map(|&x| v[x]).max().unwrap());
+            }
+        }
+        *sum.data_mut(6) += 10;
+        assert_eq!(*sum.data(6), 12);
+    }
 }
@@ -0,0 +1,503 @@
+use crate::math::num::alge_struct::types::AddMonoid;
+use crate::structs::binary_indexed_tree::BIT;
+
+use core::ops::{
+    Bound::{Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+fn to_range<R: RangeBounds<usize>>(range: R, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l + 1,
+        Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Included(&r) => r + 1,
+        Excluded(&r) => r,
+        Unbounded => n,
+    };
+    assert!(l <= r && r <= n);
+    (l, r)
+}
+
+/// A bit vector answering the number of ones in a prefix in O(1).
+struct BitVector {
+    bits: Vec<u64>,
+    // `ranks[i]` is the number of ones in `bits[..i]`
+    ranks: Vec<usize>,
+}
+
+impl BitVector {
+    fn new(v: &[bool]) -> Self {
+        let mut bits = vec![0_u64; v.len() / 64 + 1];
+        for (i, &b) in v.iter().enumerate() {
+            if b {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut ranks = vec![0; bits.len() + 1];
+        for i in 0..bits.len() {
+            ranks[i + 1] = ranks[i] + bits[i].count_ones() as usize;
+        }
+        Self { bits, ranks }
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.bits[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// Returns the number of ones in `..i`.
+    fn rank1(&self, i: usize) -> usize {
+        let mask = (1_u64 << (i % 64)) - 1;
+        self.ranks[i / 64] + (self.bits[i / 64] & mask).count_ones() as usize
+    }
+
+    /// Returns the number of zeros in `..i`.
+    fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+}
+
+/// A static structure answering the order statistics of the values in a range
+/// in O(log(*σ*)), where *σ* is the maximum value.
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::wavelet_matrix::WaveletMatrix;
+///
+/// let wm = WaveletMatrix::from(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(wm.access(4), 5);
+/// assert_eq!(wm.rank(..5, 1), 2);
+/// assert_eq!(wm.kth_smallest(2..7, 1), Some(2));
+/// assert_eq!(wm.range_freq(.., 2..6), 4);
+/// assert_eq!(wm.prev_value(..4, 4), Some(3));
+/// assert_eq!(wm.next_value(4.., 7), Some(9));
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm        | Worst case          |
+/// | ---------------- | ------------------- |
+/// | Build            | O(*n* log(*σ*))     |
+/// | [`access`]       | O(log(*σ*))         |
+/// | [`rank`]         | O(log(*σ*))         |
+/// | [`kth_smallest`] | O(log(*σ*))         |
+/// | [`range_freq`]   | O(log(*σ*))         |
+///
+/// [`access`]: WaveletMatrix::access
+/// [`rank`]: WaveletMatrix::rank
+/// [`kth_smallest`]: WaveletMatrix::kth_smallest
+/// [`range_freq`]: WaveletMatrix::range_freq
+pub struct WaveletMatrix {
+    len: usize,
+    // `bits[d]` has the `height - 1 - d`-th bits of the values sorted stably by the higher bits
+    bits: Vec<BitVector>,
+    // `zeros[d]` is the number of zeros in `bits[d]`
+    zeros: Vec<usize>,
+}
+
+impl WaveletMatrix {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn height(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Returns whether `x` is less than `2^height`.
+    fn fits(&self, x: u64) -> bool {
+        x.checked_shr(self.height() as u32).map_or(true, |y| y == 0)
+    }
+
+    /// Returns the positions at the next level of `i` whose bit at level `d` is `bit`.
+    fn next(&self, d: usize, i: usize, bit: bool) -> usize {
+        if bit {
+            self.zeros[d] + self.bits[d].rank1(i)
+        } else {
+            self.bits[d].rank0(i)
+        }
+    }
+
+    /// Returns the value at `index`.
+    pub fn access(&self, index: usize) -> u64 {
+        assert!(index < self.len);
+        let mut i = index;
+        let mut ret = 0;
+        for d in 0..self.height() {
+            let bit = self.bits[d].get(i);
+            ret = ret << 1 | bit as u64;
+            i = self.next(d, i, bit);
+        }
+        ret
+    }
+
+    /// Returns the number of `value` in `range`.
+    pub fn rank<R: RangeBounds<usize>>(&self, range: R, value: u64) -> usize {
+        let (mut l, mut r) = to_range(range, self.len);
+        if !self.fits(value) {
+            return 0;
+        }
+        for d in 0..self.height() {
+            let bit = value >> (self.height() - 1 - d) & 1 == 1;
+            l = self.next(d, l, bit);
+            r = self.next(d, r, bit);
+        }
+        r - l
+    }
+
+    /// Returns the `k`-th smallest value (0-indexed) in `range`,
+    /// or `None` if `range` has at most `k` values.
+    pub fn kth_smallest<R: RangeBounds<usize>>(&self, range: R, k: usize) -> Option<u64> {
+        let (mut l, mut r) = to_range(range, self.len);
+        if k >= r - l {
+            return None;
+        }
+        let mut k = k;
+        let mut ret = 0;
+        for d in 0..self.height() {
+            let zeros = self.bits[d].rank0(r) - self.bits[d].rank0(l);
+            let bit = k >= zeros;
+            if bit {
+                k -= zeros;
+            }
+            ret = ret << 1 | bit as u64;
+            l = self.next(d, l, bit);
+            r = self.next(d, r, bit);
+        }
+        Some(ret)
+    }
+
+    /// Returns the `k`-th largest value (0-indexed) in `range`,
+    /// or `None` if `range` has at most `k` values.
+    pub fn kth_largest<R: RangeBounds<usize>>(&self, range: R, k: usize) -> Option<u64> {
+        let (l, r) = to_range(range, self.len);
+        if k >= r - l {
+            return None;
+        }
+        self.kth_smallest(l..r, r - l - 1 - k)
+    }
+
+    /// Returns the number of values less than `upper` in `l..r`.
+    fn count_less(&self, l: usize, r: usize, upper: u64) -> usize {
+        if !self.fits(upper) {
+            return r - l;
+        }
+        let (mut l, mut r) = (l, r);
+        let mut ret = 0;
+        for d in 0..self.height() {
+            let bit = upper >> (self.height() - 1 - d) & 1 == 1;
+            if bit {
+                ret += self.bits[d].rank0(r) - self.bits[d].rank0(l);
+            }
+            l = self.next(d, l, bit);
+            r = self.next(d, r, bit);
+        }
+        ret
+    }
+
+    /// Returns the number of values in `range` which are contained in `values`.
+    pub fn range_freq<R, V>(&self, range: R, values: V) -> usize
+    where
+        R: RangeBounds<usize>,
+        V: RangeBounds<u64>,
+    {
+        let (l, r) = to_range(range, self.len);
+        let less_eq = |x: u64| x.checked_add(1).map_or(r - l, |x| self.count_less(l, r, x));
+        let lo = match values.start_bound() {
+            Included(&x) => self.count_less(l, r, x),
+            Excluded(&x) => less_eq(x),
+            Unbounded => 0,
+        };
+        let hi = match values.end_bound() {
+            Included(&x) => less_eq(x),
+            Excluded(&x) => self.count_less(l, r, x),
+            Unbounded => r - l,
+        };
+        hi.saturating_sub(lo)
+    }
+
+    /// Returns the largest value less than `upper` in `range`.
+    pub fn prev_value<R: RangeBounds<usize>>(&self, range: R, upper: u64) -> Option<u64> {
+        let (l, r) = to_range(range, self.len);
+        match self.count_less(l, r, upper) {
+            0 => None,
+            count => self.kth_smallest(l..r, count - 1),
+        }
+    }
+
+    /// Returns the smallest value not less than `lower` in `range`.
+    pub fn next_value<R: RangeBounds<usize>>(&self, range: R, lower: u64) -> Option<u64> {
+        let (l, r) = to_range(range, self.len);
+        self.kth_smallest(l..r, self.count_less(l, r, lower))
+    }
+}
+
+impl From<Vec<u64>> for WaveletMatrix {
+    fn from(v: Vec<u64>) -> Self {
+        let len = v.len();
+        let max = v.iter().copied().max().unwrap_or(0);
+        let height = (64 - max.leading_zeros()) as usize;
+        let mut bits = Vec::with_capacity(height);
+        let mut zeros = Vec::with_capacity(height);
+        let mut cur = v;
+        for d in (0..height).rev() {
+            let level = cur.iter().map(|&x| x >> d & 1 == 1).collect::<Vec<_>>();
+            let (mut next, ones): (Vec<_>, Vec<_>) =
+                cur.into_iter().partition(|&x| x >> d & 1 == 0);
+            zeros.push(next.len());
+            next.extend(ones);
+            bits.push(BitVector::new(&level));
+            cur = next;
+        }
+        Self { len, bits, zeros }
+    }
+}
+
+/// A [`WaveletMatrix`] with a weight on each element,
+/// answering the sums of the weights of the elements whose values are in a range.
+///
+/// The weights are initialized with the values, and can be increased by [`add`].
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::wavelet_matrix::WaveletMatrixSum;
+///
+/// let mut wm = WaveletMatrixSum::from(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(wm.sum(.., 2..6), 14);
+/// assert_eq!(wm.sum_smallest(..5, 3), 5);
+/// wm.add(2, 10);
+/// assert_eq!(wm.sum(.., 2..6), 24);
+/// ```
+///
+/// # Time complexity
+///
+/// | Algorithm        | Worst case                |
+/// | ---------------- | ------------------------- |
+/// | Build            | O(*n* log(*σ*))           |
+/// | [`add`]          | O(log(*n*) log(*σ*))      |
+/// | [`sum`]          | O(log(*n*) log(*σ*))      |
+/// | [`sum_smallest`] | O(log(*n*) log(*σ*))      |
+///
+/// [`add`]: WaveletMatrixSum::add
+/// [`sum`]: WaveletMatrixSum::sum
+/// [`sum_smallest`]: WaveletMatrixSum::sum_smallest
+pub struct WaveletMatrixSum {
+    matrix: WaveletMatrix,
+    // `sums[d]` has the weights in the order of `matrix.bits[d]`,
+    // and the last one has them sorted by the values
+    sums: Vec<BIT<AddMonoid<u64>>>,
+}
+
+impl WaveletMatrixSum {
+    pub fn len(&self) -> usize {
+        self.matrix.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the underlying [`WaveletMatrix`].
+    pub fn matrix(&self) -> &WaveletMatrix {
+        &self.matrix
+    }
+
+    fn fold(&self, d: usize, l: usize, r: usize) -> u64 {
+        self.sums[d].fold(..r) - self.sums[d].fold(..l)
+    }
+
+    /// Adds `weight` to the weight of the element at `index`.
+    pub fn add(&mut self, index: usize, weight: u64) {
+        assert!(index < self.len());
+        let mut i = index;
+        for d in 0..self.matrix.height() {
+            self.sums[d].operate(i, &weight);
+            i = self.matrix.next(d, i, self.matrix.bits[d].get(i));
+        }
+        self.sums[self.matrix.height()].operate(i, &weight);
+    }
+
+    /// Returns the sum of the weights of the elements less than `upper` in `l..r`.
+    fn sum_less(&self, l: usize, r: usize, upper: u64) -> u64 {
+        let matrix = &self.matrix;
+        if !matrix.fits(upper) {
+            return self.fold(0, l, r);
+        }
+        let (mut l, mut r) = (l, r);
+        let mut ret = 0;
+        for d in 0..matrix.height() {
+            let bit = upper >> (matrix.height() - 1 - d) & 1 == 1;
+            if bit {
+                ret += self.fold(d + 1, matrix.next(d, l, false), matrix.next(d, r, false));
+            }
+            l = matrix.next(d, l, bit);
+            r = matrix.next(d, r, bit);
+        }
+        ret
+    }
+
+    /// Returns the sum of the weights of the elements in `range` whose values are in `values`.
+    pub fn sum<R, V>(&self, range: R, values: V) -> u64
+    where
+        R: RangeBounds<usize>,
+        V: RangeBounds<u64>,
+    {
+        let (l, r) = to_range(range, self.len());
+        let less_eq = |x: u64| {
+            x.checked_add(1)
+                .map_or_else(|| self.fold(0, l, r), |x| self.sum_less(l, r, x))
+        };
+        let lo = match values.start_bound() {
+            Included(&x) => self.sum_less(l, r, x),
+            Excluded(&x) => less_eq(x),
+            Unbounded => 0,
+        };
+        let hi = match values.end_bound() {
+            Included(&x) => less_eq(x),
+            Excluded(&x) => self.sum_less(l, r, x),
+            Unbounded => self.fold(0, l, r),
+        };
+        hi.saturating_sub(lo)
+    }
+
+    /// Returns the sum of the weights of the `k` smallest elements in `range`,
+    /// where the elements with the same value are taken from the front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` has less than `k` elements.
+    pub fn sum_smallest<R: RangeBounds<usize>>(&self, range: R, k: usize) -> u64 {
+        let matrix = &self.matrix;
+        let (mut l, mut r) = to_range(range, self.len());
+        assert!(k <= r - l);
+        let mut k = k;
+        let mut ret = 0;
+        for d in 0..matrix.height() {
+            let (l0, r0) = (matrix.next(d, l, false), matrix.next(d, r, false));
+            let bit = k >= r0 - l0;
+            if bit {
+                k -= r0 - l0;
+                ret += self.fold(d + 1, l0, r0);
+            }
+            l = matrix.next(d, l, bit);
+            r = matrix.next(d, r, bit);
+        }
+        ret + self.fold(matrix.height(), l, l + k)
+    }
+}
+
+impl From<Vec<u64>> for WaveletMatrixSum {
+    fn from(v: Vec<u64>) -> Self {
+        let matrix = WaveletMatrix::from(v.clone());
+        let mut sums = Vec::with_capacity(matrix.height() + 1);
+        let mut cur = v;
+        sums.push(BIT::from(cur.clone()));
+        for d in (0..matrix.height()).rev() {
+            let (mut next, ones): (Vec<_>, Vec<_>) =
+                cur.into_iter().partition(|&x| x >> d & 1 == 0);
+            next.extend(ones);
+            sums.push(BIT::from(next.clone()));
+            cur = next;
+        }
+        Self { matrix, sums }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WaveletMatrix, WaveletMatrixSum};
+
+    #[test]
+    fn queries() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut rand = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+
+        let v = (0..60).map(|_| rand(40)).collect::<Vec<_>>();
+        let wm = WaveletMatrix::from(v.clone());
+        assert_eq!(wm.len(), v.len());
+        for (i, &x) in v.iter().enumerate() {
+            assert_eq!(wm.access(i), x);
+        }
+        for l in 0..=v.len() {
+            for r in l..=v.len() {
+                let mut sorted = v[l..r].to_vec();
+                sorted.sort();
+                for k in 0..=sorted.len() {
+                    assert_eq!(wm.kth_smallest(l..r, k), sorted.get(k).copied());
+                }
+                let x = rand(45);
+                let y = rand(45);
+                let count = |f: &dyn Fn(u64) -> bool| sorted.iter().filter(|&&z| f(z)).count();
+                assert_eq!(wm.rank(l..r, x), count(&|z| z == x));
+                assert_eq!(wm.range_freq(l..r, x..y), count(&|z| x <= z && z < y));
+                assert_eq!(wm.range_freq(l..r, x..=y), count(&|z| x <= z && z <= y));
+                assert_eq!(wm.range_freq(l..r, ..y), count(&|z| z < y));
+                let prev = sorted.iter().copied().filter(|&z| z < x).max();
+                let next = sorted.iter().copied().filter(|&z| z >= x).min();
+                assert_eq!(wm.prev_value(l..r, x), prev);
+                assert_eq!(wm.next_value(l..r, x), next);
+                if let Some(k) = sorted.len().checked_sub(1 + x as usize % 3) {
+                    assert_eq!(wm.kth_largest(l..r, x as usize % 3), Some(sorted[k]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extreme_values() {
+        let v = vec![0, u64::max_value(), 1 << 63, 5, u64::max_value()];
+        let wm = WaveletMatrix::from(v.clone());
+        assert_eq!(wm.access(1), u64::max_value());
+        assert_eq!(wm.rank(.., u64::max_value()), 2);
+        assert_eq!(wm.range_freq(.., 1..), 4);
+        assert_eq!(wm.range_freq(.., ..=u64::max_value()), 5);
+        assert_eq!(wm.kth_largest(.., 2), Some(1 << 63));
+
+        let zeros = WaveletMatrix::from(vec![0; 4]);
+        assert_eq!(zeros.kth_smallest(1.., 2), Some(0));
+        assert_eq!(zeros.range_freq(.., 1..), 0);
+        assert_eq!(zeros.prev_value(.., 1), Some(0));
+        assert_eq!(zeros.next_value(.., 1), None);
+    }
+
+    #[test]
+    fn sum() {
+        let v = (0..50).map(|i| (i * 37 + 11) % 29).collect::<Vec<u64>>();
+        let mut w = v.clone();
+        let mut wm = WaveletMatrixSum::from(v.clone());
+        for i in (0..v.len()).step_by(7) {
+            wm.add(i, i as u64);
+            w[i] += i as u64;
+        }
+        for l in 0..=v.len() {
+            for r in l..=v.len() {
+                for &(x, y) in &[(0, 30), (5, 17), (12, 12), (20, 29)] {
+                    let expected = (l..r)
+                        .filter(|&i| x <= v[i] && v[i] < y)
+                        .map(|i| w[i])
+                        .sum::<u64>();
+                    assert_eq!(wm.sum(l..r, x..y), expected);
+                }
+                let mut sorted = (l..r).collect::<Vec<_>>();
+                sorted.sort_by_key(|&i| v[i]);
+                for k in 0..=sorted.len() {
+                    let expected = sorted[..k].iter().map(|&i| w[i]).sum::<u64>();
+                    assert_eq!(wm.sum_smallest(l..r, k), expected);
+                }
+            }
+        }
+    }
+}
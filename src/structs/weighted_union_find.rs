@@ -0,0 +1,175 @@
+use crate::math::num::Group;
+
+/// A union-find tracking the potential differences between the elements.
+///
+/// `diff(a, b)` is `inv(p(a)) ∘ p(b)` for the potential `p`,
+/// i.e. `p(b) - p(a)` for [`AddGroup`].
+///
+/// [`AddGroup`]: crate::math::num::alge_struct::types::AddGroup
+///
+/// # Examples
+///
+/// ```
+/// use lib_rust::structs::weighted_union_find::WeightedUnionFind;
+/// use lib_rust::math::num::alge_struct::types::AddGroup;
+///
+/// let mut uf = WeightedUnionFind::<AddGroup<i64>>::new(4);
+/// assert_eq!(uf.union(0, 1, 3), Ok(true));
+/// assert_eq!(uf.union(1, 2, 4), Ok(true));
+/// assert_eq!(uf.diff(0, 2), Some(7));
+/// assert_eq!(uf.diff(0, 3), None);
+/// assert_eq!(uf.union(2, 0, -7), Ok(false));
+/// assert_eq!(uf.union(2, 0, 0), Err(-7));
+/// ```
+pub struct WeightedUnionFind<G: Group> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    // the difference from the parent
+    weight: Vec<G::Set>,
+}
+
+impl<G: Group> WeightedUnionFind<G> {
+    pub fn new(n: usize) -> Self {
+        let parent = (0..n).collect::<Vec<_>>();
+        let size = vec![1; n];
+        let weight = vec![G::id(); n];
+        Self {
+            parent,
+            size,
+            weight,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn find(&self, x: usize) -> usize {
+        self.potential(x).0
+    }
+
+    pub fn find_mut(&mut self, x: usize) -> usize {
+        assert!(x < self.parent.len());
+        let mut path = vec![];
+        let mut root = x;
+        while self.parent[root] != root {
+            path.push(root);
+            root = self.parent[root];
+        }
+        for &y in path.iter().rev() {
+            let parent = self.parent[y];
+            if parent != root {
+                self.weight[y] = G::operate(&self.weight[parent], &self.weight[y]);
+                self.parent[y] = root;
+            }
+        }
+        root
+    }
+
+    /// Returns the root of `x` and the difference from the root to `x`.
+    fn potential(&self, x: usize) -> (usize, G::Set) {
+        assert!(x < self.parent.len());
+        let mut x = x;
+        let mut ret = G::id();
+        while self.parent[x] != x {
+            ret = G::operate(&self.weight[x], &ret);
+            x = self.parent[x];
+        }
+        (x, ret)
+    }
+
+    pub fn equiv(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// Returns the difference from `a` to `b` if they are in the same group.
+    pub fn diff(&self, a: usize, b: usize) -> Option<G::Set> {
+        let (ra, pa) = self.potential(a);
+        let (rb, pb) = self.potential(b);
+        if ra == rb {
+            Some(G::operate(&G::inv(&pa), &pb))
+        } else {
+            None
+        }
+    }
+
+    /// Merges the groups of `a` and `b` so that `diff(a, b)` is `w`.
+    ///
+    /// Returns whether the groups are merged,
+    /// or `Err` with the existing difference if it contradicts `w`.
+    pub fn union(&mut self, a: usize, b: usize, w: G::Set) -> Result<bool, G::Set>
+    where
+        G::Set: PartialEq,
+    {
+        let ra = self.find_mut(a);
+        let rb = self.find_mut(b);
+        let (_, pa) = self.potential(a);
+        let (_, pb) = self.potential(b);
+        if ra == rb {
+            let diff = G::operate(&G::inv(&pa), &pb);
+            return if diff == w { Ok(false) } else { Err(diff) };
+        }
+
+        // the difference from `ra` to `rb`
+        let w = G::operate(&G::operate(&pa, &w), &G::inv(&pb));
+        if self.size[ra] < self.size[rb] {
+            self.parent[ra] = rb;
+            self.size[rb] += self.size[ra];
+            self.weight[ra] = G::inv(&w);
+        } else {
+            self.parent[rb] = ra;
+            self.size[ra] += self.size[rb];
+            self.weight[rb] = w;
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::num::alge_struct::types::{AddGroup, BitXorGroup};
+
+    #[test]
+    fn potential() {
+        let n = 20;
+        let p = (0..n).map(|i| (i * i % 17) as i64).collect::<Vec<_>>();
+        let mut uf = WeightedUnionFind::<AddGroup<i64>>::new(n);
+        for i in (0..n).rev().skip(1) {
+            let j = (i * 7 + 3) % n;
+            assert!(uf.union(i, j, p[j] - p[i]).is_ok());
+        }
+        for a in 0..n {
+            for b in 0..n {
+                match uf.diff(a, b) {
+                    Some(d) => {
+                        assert!(uf.equiv(a, b));
+                        assert_eq!(d, p[b] - p[a]);
+                        assert_eq!(uf.union(a, b, d + 1), Err(d));
+                    }
+                    None => assert!(!uf.equiv(a, b)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parity() {
+        let mut uf = WeightedUnionFind::<BitXorGroup<u8>>::new(5);
+        assert_eq!(uf.union(0, 1, 1), Ok(true));
+        assert_eq!(uf.union(1, 2, 1), Ok(true));
+        assert_eq!(uf.union(3, 4, 0), Ok(true));
+        assert_eq!(uf.union(4, 2, 1), Ok(true));
+        assert_eq!(uf.diff(0, 3), Some(1));
+        assert_eq!(uf.union(0, 2, 1), Err(0));
+        assert_eq!(uf.size(4), 5);
+    }
+}